@@ -0,0 +1,51 @@
+use simple_si_units::base::Temperature;
+
+/*
+ * Ballesteros' formula relating the B-V color index to the effective temperature of a black body:
+ * https://arxiv.org/abs/1201.1809
+ */
+pub fn b_minus_v_to_temperature(b_minus_v: f64) -> Temperature<f64> {
+    let x = 0.92 * b_minus_v;
+    Temperature::from_K(4600. * (1. / (x + 1.7) + 1. / (x + 0.62)))
+}
+
+/*
+ * The inverse of Ballesteros' formula, obtained by solving the resulting quadratic equation.
+ */
+pub fn temperature_to_b_minus_v(temperature: Temperature<f64>) -> f64 {
+    let k = temperature.K / 4600.;
+    let a = k;
+    let b = 2.32 * k - 2.;
+    let c = 1.054 * k - 2.32;
+    let x = (-b + (b * b - 4. * a * c).sqrt()) / (2. * a);
+    x / 0.92
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::eq_within;
+
+    #[test]
+    fn sun_has_b_minus_v_of_0_65() {
+        let b_minus_v = temperature_to_b_minus_v(Temperature::from_K(5778.));
+        assert!(eq_within(b_minus_v, 0.65, 0.02));
+    }
+
+    #[test]
+    fn b_minus_v_roundtrip() {
+        for i in -3..20 {
+            let input = i as f64 / 10.;
+            let temperature = b_minus_v_to_temperature(input);
+            let output = temperature_to_b_minus_v(temperature);
+            assert!(eq_within(input, output, 1e-6));
+        }
+    }
+
+    #[test]
+    fn hotter_stars_are_bluer() {
+        let cool = temperature_to_b_minus_v(Temperature::from_K(3500.));
+        let hot = temperature_to_b_minus_v(Temperature::from_K(10_000.));
+        assert!(hot < cool);
+    }
+}
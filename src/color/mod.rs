@@ -1,4 +1,5 @@
 pub mod black_body;
+pub mod color_index;
 pub(crate) mod color_matching_functions;
 pub mod srgb;
 mod xyz;
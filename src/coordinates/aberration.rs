@@ -0,0 +1,54 @@
+use astro_coords::direction::Direction;
+use simple_si_units::mechanical::Velocity;
+
+/*
+ * Shifts the direction towards a star to where it appears for an observer moving with the given velocity, whose components are given in the same frame as dir.
+ * For Earth's orbital motion, the shift is at most about 20.5 arcseconds:
+ * https://en.wikipedia.org/wiki/Aberration_(astronomy)
+ * The formula is accurate to first order in v/c, which for Earth means a few thousandths of an arcsecond.
+ */
+pub fn apply_aberration(dir: &Direction, observer_velocity: &[Velocity<f64>; 3]) -> Direction {
+    let u = [dir.x(), dir.y(), dir.z()];
+    let beta = observer_velocity.map(|v| v.to_c());
+    let projection = u[0] * beta[0] + u[1] * beta[1] + u[2] * beta[2];
+    let shifted: [f64; 3] = std::array::from_fn(|i| u[i] + beta[i] - projection * u[i]);
+    Direction::new(shifted[0], shifted[1], shifted[2]).unwrap_or_else(|_| dir.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::projection::{components, cross, dot},
+        units::angle::angle_to_arcsecs,
+    };
+    use simple_si_units::geometry::Angle;
+
+    fn earth_velocity_along_y() -> [Velocity<f64>; 3] {
+        [
+            Velocity::from_kmps(0.),
+            Velocity::from_kmps(29.78),
+            Velocity::from_kmps(0.),
+        ]
+    }
+
+    fn separation_in_arcsecs(a: &Direction, b: &Direction) -> f64 {
+        let sine = cross(&components(a), &components(b));
+        let sine = dot(&sine, &sine).sqrt();
+        angle_to_arcsecs(&Angle { rad: sine.asin() })
+    }
+
+    #[test]
+    fn star_perpendicular_to_motion_is_shifted_by_aberration_constant() {
+        let shifted = apply_aberration(&Direction::X, &earth_velocity_along_y());
+        let shift = separation_in_arcsecs(&Direction::X, &shifted);
+        assert!((shift - 20.5).abs() < 0.05);
+        assert!(shifted.y() > 0.);
+    }
+
+    #[test]
+    fn star_along_the_motion_is_not_shifted() {
+        let shifted = apply_aberration(&Direction::Y, &earth_velocity_along_y());
+        assert!(separation_in_arcsecs(&Direction::Y, &shifted) < 1e-6);
+    }
+}
@@ -0,0 +1,191 @@
+use astro_coords::direction::Direction;
+use simple_si_units::{base::Time, geometry::Angle};
+
+use super::{
+    nutation::{nutation, true_obliquity},
+    projection::components,
+    rotation::Rotation,
+};
+use crate::{
+    error::AstroUtilError,
+    units::{
+        angle::{angle_from_arcsecs, DEGREE},
+        time::JULIAN_CENTURY,
+    },
+};
+
+pub(crate) const EARTH_OBLIQUITY: Angle<f64> = Angle {
+    rad: 23.439_281 * DEGREE.rad,
+};
+
+/*
+ * The mean obliquity of the ecliptic, following the IAU 2006 precession model:
+ * https://doi.org/10.1007/s10569-006-0004-z
+ * The time is counted from J2000. The polynomial is accurate within a few thousand years of it.
+ */
+pub fn earth_obliquity(time_since_j2000: Time<f64>) -> Angle<f64> {
+    let t = time_since_j2000 / JULIAN_CENTURY;
+    let arcsecs = 84381.406
+        + t * (-46.836769 + t * (-0.0001831 + t * (0.00200340 + t * (-5.76e-7 + t * -4.34e-8))));
+    angle_from_arcsecs(arcsecs)
+}
+
+/*
+ * Converts a right ascension and declination, measured against Earth's equator at the given time since J2000, into a direction in the ecliptic frame.
+ */
+pub fn earth_equatorial_to_direction_at(
+    right_ascension: Angle<f64>,
+    declination: Angle<f64>,
+    time_since_j2000: Time<f64>,
+) -> Result<Direction, AstroUtilError> {
+    let equatorial = equatorial_components(right_ascension, declination);
+    rotate_equatorial_to_ecliptic(equatorial, earth_obliquity(time_since_j2000))
+}
+
+/*
+ * Like earth_equatorial_to_direction_at, but for apparent coordinates, which are measured against the true equator and equinox.
+ * Nutation is removed, so the result is in the mean ecliptic frame of the given time.
+ */
+pub fn apparent_earth_equatorial_to_direction_at(
+    right_ascension: Angle<f64>,
+    declination: Angle<f64>,
+    time_since_j2000: Time<f64>,
+) -> Result<Direction, AstroUtilError> {
+    let equatorial = equatorial_components(right_ascension, declination);
+    let dir = rotate_equatorial_to_ecliptic(equatorial, true_obliquity(time_since_j2000))?;
+    let (nutation_in_longitude, _) = nutation(time_since_j2000);
+    let rotation = Rotation::from_axis_angle(
+        &Direction::Z,
+        Angle {
+            rad: -nutation_in_longitude.rad,
+        },
+    );
+    Ok(rotation.apply(&dir))
+}
+
+fn equatorial_components(right_ascension: Angle<f64>, declination: Angle<f64>) -> [f64; 3] {
+    let (sin_ra, cos_ra) = right_ascension.rad.sin_cos();
+    let (sin_dec, cos_dec) = declination.rad.sin_cos();
+    [cos_dec * cos_ra, cos_dec * sin_ra, sin_dec]
+}
+
+/*
+ * Rotates cartesian components given in the frame of Earth's equator into the ecliptic frame.
+ */
+pub(crate) fn equatorial_to_ecliptic(equatorial: [f64; 3]) -> Result<Direction, AstroUtilError> {
+    rotate_equatorial_to_ecliptic(equatorial, EARTH_OBLIQUITY)
+}
+
+fn rotate_equatorial_to_ecliptic(
+    equatorial: [f64; 3],
+    obliquity: Angle<f64>,
+) -> Result<Direction, AstroUtilError> {
+    let [x, y, z] = equatorial_to_ecliptic_rotation(obliquity).apply_to_components(equatorial);
+    Ok(Direction::new(x, y, z)?)
+}
+
+fn equatorial_to_ecliptic_rotation(obliquity: Angle<f64>) -> Rotation {
+    Rotation::from_axis_angle(
+        &Direction::X,
+        Angle {
+            rad: -obliquity.rad,
+        },
+    )
+}
+
+/*
+ * The inverse of equatorial_to_ecliptic.
+ */
+pub(crate) fn ecliptic_to_equatorial(dir: &Direction) -> [f64; 3] {
+    equatorial_to_ecliptic_rotation(EARTH_OBLIQUITY)
+        .inverse()
+        .apply_to_components(components(dir))
+}
+
+/*
+ * Returns the right ascension, between 0 and 360 degrees, and the declination of a direction in the ecliptic frame.
+ */
+pub fn right_ascension_and_declination(dir: &Direction) -> (Angle<f64>, Angle<f64>) {
+    let [x, y, z] = ecliptic_to_equatorial(dir);
+    let right_ascension = y.atan2(x).rem_euclid(2. * std::f64::consts::PI);
+    let declination = z.clamp(-1., 1.).asin();
+    (
+        Angle {
+            rad: right_ascension,
+        },
+        Angle { rad: declination },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::eq,
+        units::{angle::angle_to_arcsecs, time::TIME_ZERO},
+    };
+
+    #[test]
+    fn obliquity_at_j2000() {
+        let obliquity = earth_obliquity(TIME_ZERO);
+        assert!((obliquity.to_degrees() - 23.4393).abs() < 1e-4);
+        assert!((obliquity - EARTH_OBLIQUITY).rad.abs() < angle_from_arcsecs(1.).rad);
+    }
+
+    #[test]
+    fn obliquity_changes_by_arcminutes_over_millenia() {
+        let now = earth_obliquity(TIME_ZERO);
+        let later = earth_obliquity(Time::from_yr(2000.));
+        let change_in_arcmin = angle_to_arcsecs(&(now - later)) / 60.;
+        assert!(change_in_arcmin > 5.);
+        assert!(change_in_arcmin < 30.);
+    }
+
+    #[test]
+    fn equatorial_coordinates_at_j2000_match_fixed_obliquity() {
+        let right_ascension = Angle::from_degrees(101.287);
+        let declination = Angle::from_degrees(-16.716);
+        let dir =
+            earth_equatorial_to_direction_at(right_ascension, declination, TIME_ZERO).unwrap();
+        let (ra, dec) = right_ascension_and_declination(&dir);
+        assert!((ra - right_ascension).rad.abs() < angle_from_arcsecs(1.).rad);
+        assert!((dec - declination).rad.abs() < angle_from_arcsecs(1.).rad);
+    }
+
+    #[test]
+    fn nutation_shifts_apparent_coordinates_by_arcseconds() {
+        let right_ascension = Angle::from_degrees(101.287);
+        let declination = Angle::from_degrees(-16.716);
+        let time = Time::from_yr(10.);
+        let mean = earth_equatorial_to_direction_at(right_ascension, declination, time).unwrap();
+        let apparent =
+            apparent_earth_equatorial_to_direction_at(right_ascension, declination, time).unwrap();
+        let [x, y, z] = [
+            mean.x() - apparent.x(),
+            mean.y() - apparent.y(),
+            mean.z() - apparent.z(),
+        ];
+        let shift = angle_to_arcsecs(&Angle {
+            rad: (x * x + y * y + z * z).sqrt(),
+        });
+        assert!(shift > 0.1);
+        assert!(shift < 30.);
+    }
+
+    #[test]
+    fn roundtrip_through_the_equatorial_frame() {
+        let dir = Direction::new(1., -2., 3.).unwrap();
+        let back = equatorial_to_ecliptic(ecliptic_to_equatorial(&dir)).unwrap();
+        assert!(dir.eq_within(&back, 1e-10));
+    }
+
+    #[test]
+    fn ecliptic_north_pole_has_declination_of_complementary_obliquity() {
+        let (right_ascension, declination) = right_ascension_and_declination(&Direction::Z);
+        assert!(eq(right_ascension.to_degrees(), 270.));
+        assert!(eq(
+            declination.to_degrees(),
+            90. - EARTH_OBLIQUITY.to_degrees()
+        ));
+    }
+}
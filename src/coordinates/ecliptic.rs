@@ -0,0 +1,56 @@
+use astro_coords::cartesian::Cartesian;
+use simple_si_units::base::Distance;
+
+/*
+ * Ecliptic coordinates are provided by astro_coords, including angle_to and eq_within.
+ * They are re-exported here so that code using this crate can rely on them, and the tests below pin down the behaviour this crate builds on.
+ */
+pub use astro_coords::ecliptic::Ecliptic;
+
+/*
+ * The point at the given distance from the origin, in the direction of the ecliptic coordinates.
+ */
+pub fn ecliptic_to_cartesian(ecliptic: &Ecliptic, distance: Distance<f64>) -> Cartesian {
+    ecliptic.spherical.to_direction().to_cartesian(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::eq,
+        units::{
+            angle::{angle_eq_within, ANGLE_ZERO},
+            distance::LIGHT_YEAR,
+            tests::ANGLE_TEST_ACCURACY,
+        },
+    };
+    use simple_si_units::geometry::Angle;
+
+    #[test]
+    fn identical_coordinates_have_no_separation() {
+        let angle = Ecliptic::X_DIRECTION.angle_to(&Ecliptic::X_DIRECTION);
+        assert!(angle_eq_within(angle, ANGLE_ZERO, ANGLE_TEST_ACCURACY));
+        assert!(Ecliptic::X_DIRECTION.eq_within(&Ecliptic::X_DIRECTION, ANGLE_TEST_ACCURACY));
+    }
+
+    #[test]
+    fn x_and_y_direction_are_perpendicular() {
+        let angle = Ecliptic::X_DIRECTION.angle_to(&Ecliptic::Y_DIRECTION);
+        assert!(angle_eq_within(
+            angle,
+            Angle::from_degrees(90.),
+            ANGLE_TEST_ACCURACY
+        ));
+        assert!(!Ecliptic::X_DIRECTION.eq_within(&Ecliptic::Y_DIRECTION, ANGLE_TEST_ACCURACY));
+    }
+
+    #[test]
+    fn x_direction_at_one_light_year() {
+        let cartesian = ecliptic_to_cartesian(&Ecliptic::X_DIRECTION, LIGHT_YEAR);
+        assert!(eq(cartesian.length() / LIGHT_YEAR, 1.));
+        assert!(eq(cartesian.x / LIGHT_YEAR, 1.));
+        assert!(eq(cartesian.y / LIGHT_YEAR, 0.));
+        assert!(eq(cartesian.z / LIGHT_YEAR, 0.));
+    }
+}
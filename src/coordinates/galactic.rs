@@ -0,0 +1,58 @@
+use astro_coords::direction::Direction;
+use simple_si_units::geometry::Angle;
+
+use super::{
+    earth_equatorial::equatorial_to_ecliptic,
+    projection::{components, dot},
+};
+use crate::units::angle::DEGREE;
+
+/*
+ * The right ascension and declination (J2000) of the north pole of the galactic coordinate system:
+ * https://en.wikipedia.org/wiki/Galactic_coordinate_system
+ */
+const NORTH_GALACTIC_POLE_RIGHT_ASCENSION: Angle<f64> = Angle {
+    rad: 192.859_48 * DEGREE.rad,
+};
+const NORTH_GALACTIC_POLE_DECLINATION: Angle<f64> = Angle {
+    rad: 27.128_25 * DEGREE.rad,
+};
+
+/*
+ * The direction of the north galactic pole in the ecliptic frame.
+ */
+pub fn north_galactic_pole() -> Direction {
+    let (sin_ra, cos_ra) = NORTH_GALACTIC_POLE_RIGHT_ASCENSION.rad.sin_cos();
+    let (sin_dec, cos_dec) = NORTH_GALACTIC_POLE_DECLINATION.rad.sin_cos();
+    equatorial_to_ecliptic([cos_dec * cos_ra, cos_dec * sin_ra, sin_dec]).unwrap_or(Direction::Z)
+}
+
+/*
+ * The angle between dir and the galactic plane, positive towards the north galactic pole.
+ */
+pub fn galactic_latitude(dir: &Direction) -> Angle<f64> {
+    let sine = dot(&components(dir), &components(&north_galactic_pole()));
+    Angle {
+        rad: sine.clamp(-1., 1.).asin(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::earth_equatorial::earth_equatorial_to_direction_at, units::time::TIME_ZERO,
+    };
+
+    #[test]
+    fn galactic_center_lies_in_the_galactic_plane() {
+        let galactic_center = earth_equatorial_to_direction_at(
+            Angle::from_degrees(266.405),
+            Angle::from_degrees(-28.936),
+            TIME_ZERO,
+        )
+        .unwrap();
+        assert!(galactic_latitude(&galactic_center).to_degrees().abs() < 0.01);
+        assert!((galactic_latitude(&north_galactic_pole()).to_degrees() - 90.).abs() < 1e-5);
+    }
+}
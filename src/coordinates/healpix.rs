@@ -0,0 +1,99 @@
+use astro_coords::direction::Direction;
+use std::f64::consts::PI;
+
+/*
+ * Bins directions into equal-area pixels, following the HEALPix ring scheme:
+ * https://healpix.jpl.nasa.gov/
+ * https://iopscience.iop.org/article/10.1086/427976/pdf
+ * The sphere is divided into 12 * nside^2 pixels, numbered ring by ring from the north pole.
+ */
+
+pub fn number_of_sky_pixels(nside: u32) -> u64 {
+    12 * (nside as u64).pow(2)
+}
+
+pub fn sky_pixel(dir: &Direction, nside: u32) -> u64 {
+    let nside = nside.max(1) as i64;
+    let z = dir.z().clamp(-1., 1.);
+    let phi = dir.y().atan2(dir.x()).rem_euclid(2. * PI);
+    let z_abs = z.abs();
+    let tt = phi / (PI / 2.); // in [0,4)
+
+    let pixel = if z_abs <= 2. / 3. {
+        // Equatorial region
+        let nside_f = nside as f64;
+        let temp1 = nside_f * (0.5 + tt);
+        let temp2 = nside_f * z * 0.75;
+        let jp = (temp1 - temp2) as i64; // index of ascending edge line
+        let jm = (temp1 + temp2) as i64; // index of descending edge line
+        let ring = nside + 1 + jp - jm; // in {1, 2n+1}
+        let kshift = 1 - (ring & 1);
+        let ip = (jp + jm - nside + kshift + 1) / 2;
+        let ip = ip.rem_euclid(4 * nside);
+        let number_of_cap_pixels = 2 * nside * (nside - 1);
+        number_of_cap_pixels + (ring - 1) * 4 * nside + ip
+    } else {
+        // Polar caps
+        let tp = tt - tt.floor();
+        let tmp = nside as f64 * (3. * (1. - z_abs)).sqrt();
+        let jp = (tp * tmp) as i64;
+        let jm = ((1. - tp) * tmp) as i64;
+        let ring = jp + jm + 1; // counted from the closest pole
+        let ip = ((tt * ring as f64) as i64).rem_euclid(4 * ring);
+        if z > 0. {
+            2 * ring * (ring - 1) + ip
+        } else {
+            12 * nside * nside - 2 * ring * (ring + 1) + ip
+        }
+    };
+    pixel as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stars::random::random_stars::random_direction;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn number_of_pixels_for_nside_1_is_12() {
+        assert_eq!(number_of_sky_pixels(1), 12);
+    }
+
+    #[test]
+    fn pixel_indices_are_within_range() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for nside in [1, 2, 4, 16] {
+            for _ in 0..1_000 {
+                let dir = random_direction(&mut rng);
+                assert!(sky_pixel(&dir, nside) < number_of_sky_pixels(nside));
+            }
+        }
+    }
+
+    #[test]
+    fn pixel_index_is_stable() {
+        let dir = Direction::new(0.3, -0.4, 0.5).unwrap();
+        assert_eq!(sky_pixel(&dir, 8), sky_pixel(&dir, 8));
+        assert!(sky_pixel(&Direction::Z, 8) < 4);
+        assert!(sky_pixel(&-&Direction::Z, 8) >= number_of_sky_pixels(8) - 4);
+    }
+
+    #[test]
+    fn all_pixels_cover_approximately_equal_solid_angle() {
+        let nside = 2;
+        let samples_per_pixel = 1_000;
+        let number_of_pixels = number_of_sky_pixels(nside) as usize;
+        let mut counts = vec![0; number_of_pixels];
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..number_of_pixels * samples_per_pixel {
+            let dir = random_direction(&mut rng);
+            counts[sky_pixel(&dir, nside) as usize] += 1;
+        }
+        for (pixel, count) in counts.iter().enumerate() {
+            println!("pixel {}: {}", pixel, count);
+            assert!(*count > samples_per_pixel * 8 / 10);
+            assert!(*count < samples_per_pixel * 12 / 10);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use astro_coords::direction::Direction;
+use simple_si_units::geometry::Angle;
+
+use super::earth_equatorial::equatorial_to_ecliptic;
+use crate::{error::AstroUtilError, units::angle::angle_from_arcsecs};
+
+/*
+ * Converts horizontal coordinates of an observer on Earth into a direction in the ecliptic frame.
+ * The azimuth is measured from north towards east.
+ * The local sidereal time is the right ascension currently crossing the observer's meridian.
+ */
+pub fn direction_from_horizontal(
+    azimuth: Angle<f64>,
+    altitude: Angle<f64>,
+    observer_latitude: Angle<f64>,
+    local_sidereal_time: Angle<f64>,
+) -> Result<Direction, AstroUtilError> {
+    let (sin_lat, cos_lat) = observer_latitude.rad.sin_cos();
+    let (sin_lst, cos_lst) = local_sidereal_time.rad.sin_cos();
+    let north = [-sin_lat * cos_lst, -sin_lat * sin_lst, cos_lat];
+    let east = [-sin_lst, cos_lst, 0.];
+    let up = [cos_lat * cos_lst, cos_lat * sin_lst, sin_lat];
+
+    let to_north = altitude.rad.cos() * azimuth.rad.cos();
+    let to_east = altitude.rad.cos() * azimuth.rad.sin();
+    let to_up = altitude.rad.sin();
+    let equatorial: [f64; 3] =
+        std::array::from_fn(|i| to_north * north[i] + to_east * east[i] + to_up * up[i]);
+
+    equatorial_to_ecliptic(equatorial)
+}
+
+/*
+ * Bennett's formula for the atmospheric refraction of an object seen at the given apparent altitude:
+ * https://en.wikipedia.org/wiki/Atmospheric_refraction#Calculating_refraction
+ * Below a few degrees under the horizon the formula diverges, so the altitude is clamped there.
+ */
+pub fn refraction(apparent_altitude: Angle<f64>) -> Angle<f64> {
+    let h = apparent_altitude.to_degrees().max(-1.);
+    if h >= 90. {
+        return Angle::from_degrees(0.);
+    }
+    let arcmin = 1. / (h + 7.31 / (h + 4.4)).to_radians().tan();
+    angle_from_arcsecs(60. * arcmin)
+}
+
+/*
+ * The altitude at which an object at the given geometric altitude appears due to refraction.
+ * Bennett's formula is inverted by fixed point iteration, which converges quickly because refraction changes slowly with altitude.
+ */
+pub fn apparent_altitude(true_altitude: Angle<f64>) -> Angle<f64> {
+    let mut apparent = true_altitude;
+    for _ in 0..10 {
+        apparent = true_altitude + refraction(apparent);
+    }
+    apparent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coordinates::earth_equatorial::EARTH_OBLIQUITY, units::angle::ANGLE_ZERO};
+
+    const ACCURACY: f64 = 1e-5;
+
+    #[test]
+    fn zenith_at_north_pole_is_celestial_north_pole() {
+        let dir = direction_from_horizontal(
+            ANGLE_ZERO,
+            Angle::from_degrees(90.),
+            Angle::from_degrees(90.),
+            Angle::from_degrees(123.),
+        )
+        .unwrap();
+        let (sin_obl, cos_obl) = EARTH_OBLIQUITY.rad.sin_cos();
+        let expected = Direction::new(0., sin_obl, cos_obl).unwrap();
+        assert!(dir.eq_within(&expected, ACCURACY));
+    }
+
+    #[test]
+    fn zenith_does_not_depend_on_azimuth() {
+        let latitude = Angle::from_degrees(48.);
+        let sidereal_time = Angle::from_degrees(200.);
+        let zenith = direction_from_horizontal(
+            ANGLE_ZERO,
+            Angle::from_degrees(90.),
+            latitude,
+            sidereal_time,
+        )
+        .unwrap();
+        for azimuth in [45., 90., 180., 270.] {
+            let dir = direction_from_horizontal(
+                Angle::from_degrees(azimuth),
+                Angle::from_degrees(90.),
+                latitude,
+                sidereal_time,
+            )
+            .unwrap();
+            assert!(dir.eq_within(&zenith, ACCURACY));
+        }
+    }
+
+    #[test]
+    fn zenith_at_equator_and_vernal_equinox_is_x() {
+        let dir =
+            direction_from_horizontal(ANGLE_ZERO, Angle::from_degrees(90.), ANGLE_ZERO, ANGLE_ZERO)
+                .unwrap();
+        assert!(dir.eq_within(&Direction::X, ACCURACY));
+    }
+
+    #[test]
+    fn refraction_at_the_horizon_is_about_34_arcminutes() {
+        let lift = refraction(ANGLE_ZERO);
+        assert!(crate::tests::eq_within(lift.to_degrees() * 60., 34.5, 0.5));
+    }
+
+    #[test]
+    fn object_34_arcminutes_below_horizon_appears_on_it() {
+        let true_altitude = Angle::from_degrees(-34.5 / 60.);
+        let apparent = apparent_altitude(true_altitude);
+        assert!(crate::tests::eq_within(apparent.to_degrees() * 60., 0., 1.));
+    }
+
+    #[test]
+    fn object_on_the_horizon_is_lifted_by_about_half_a_degree() {
+        let lift = apparent_altitude(ANGLE_ZERO) - ANGLE_ZERO;
+        let arcmin = lift.to_degrees() * 60.;
+        assert!(arcmin > 25. && arcmin < 36.);
+    }
+
+    #[test]
+    fn high_objects_are_barely_affected() {
+        let true_altitude = Angle::from_degrees(80.);
+        let lift = apparent_altitude(true_altitude) - true_altitude;
+        assert!(lift.to_degrees() * 60. < 0.25);
+        assert!(lift.rad > 0.);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod aberration;
+pub mod earth_equatorial;
+pub mod ecliptic;
+pub mod galactic;
+pub mod healpix;
+pub mod horizontal;
+pub mod nutation;
+pub mod projection;
+pub mod quaternion;
+pub mod rotation;
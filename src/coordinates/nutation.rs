@@ -0,0 +1,102 @@
+use simple_si_units::{base::Time, geometry::Angle};
+
+use super::earth_equatorial::earth_obliquity;
+use crate::units::{angle::angle_from_arcsecs, time::JULIAN_CENTURY};
+
+/*
+ * The largest terms of the IAU 1980 nutation series, as listed in Meeus, Astronomical Algorithms, table 22.A.
+ * Each row holds the multiples of D, M, M', F and Ω, followed by the sine coefficients for longitude and the cosine coefficients for obliquity in units of 0.0001 arcseconds (constant and per Julian century).
+ * The omitted terms contribute a few hundredths of an arcsecond at most.
+ */
+const NUTATION_TERMS: [([f64; 5], [f64; 4]); 18] = [
+    ([0., 0., 0., 0., 1.], [-171996., -174.2, 92025., 8.9]),
+    ([-2., 0., 0., 2., 2.], [-13187., -1.6, 5736., -3.1]),
+    ([0., 0., 0., 2., 2.], [-2274., -0.2, 977., -0.5]),
+    ([0., 0., 0., 0., 2.], [2062., 0.2, -895., 0.5]),
+    ([0., 1., 0., 0., 0.], [1426., -3.4, 54., -0.1]),
+    ([0., 0., 1., 0., 0.], [712., 0.1, -7., 0.]),
+    ([-2., 1., 0., 2., 2.], [-517., 1.2, 224., -0.6]),
+    ([0., 0., 0., 2., 1.], [-386., -0.4, 200., 0.]),
+    ([0., 0., 1., 2., 2.], [-301., 0., 129., -0.1]),
+    ([-2., -1., 0., 2., 2.], [217., -0.5, -95., 0.3]),
+    ([-2., 0., 1., 0., 0.], [-158., 0., 0., 0.]),
+    ([-2., 0., 0., 2., 1.], [129., 0.1, -70., 0.]),
+    ([0., 0., -1., 2., 2.], [123., 0., -53., 0.]),
+    ([2., 0., 0., 0., 0.], [63., 0., 0., 0.]),
+    ([0., 0., 1., 0., 1.], [63., 0.1, -33., 0.]),
+    ([2., 0., -1., 2., 2.], [-59., 0., 26., 0.]),
+    ([0., 0., -1., 0., 1.], [-58., -0.1, 32., 0.]),
+    ([0., 0., 1., 2., 1.], [-51., 0., 27., 0.]),
+];
+
+/*
+ * The nutation in longitude and in obliquity at the given time since J2000.
+ */
+pub fn nutation(time_since_j2000: Time<f64>) -> (Angle<f64>, Angle<f64>) {
+    let t = time_since_j2000 / JULIAN_CENTURY;
+    let cubic = |c: [f64; 4]| c[0] + t * (c[1] + t * (c[2] + t * c[3]));
+    let arguments = [
+        // Mean elongation of the Moon from the Sun
+        cubic([297.85036, 445267.111480, -0.0019142, 1. / 189474.]),
+        // Mean anomaly of the Sun
+        cubic([357.52772, 35999.050340, -0.0001603, -1. / 300000.]),
+        // Mean anomaly of the Moon
+        cubic([134.96298, 477198.867398, 0.0086972, 1. / 56250.]),
+        // Moon's argument of latitude
+        cubic([93.27191, 483202.017538, -0.0036825, 1. / 327270.]),
+        // Longitude of the ascending node of the Moon's orbit
+        cubic([125.04452, -1934.136261, 0.0020708, 1. / 450000.]),
+    ]
+    .map(f64::to_radians);
+
+    let mut longitude = 0.;
+    let mut obliquity = 0.;
+    for (multiples, coefficients) in NUTATION_TERMS.iter() {
+        let argument: f64 = multiples
+            .iter()
+            .zip(arguments.iter())
+            .map(|(multiple, argument)| multiple * argument)
+            .sum();
+        longitude += (coefficients[0] + coefficients[1] * t) * argument.sin();
+        obliquity += (coefficients[2] + coefficients[3] * t) * argument.cos();
+    }
+    (
+        angle_from_arcsecs(longitude * 1e-4),
+        angle_from_arcsecs(obliquity * 1e-4),
+    )
+}
+
+/*
+ * The obliquity of the ecliptic including nutation.
+ */
+pub fn true_obliquity(time_since_j2000: Time<f64>) -> Angle<f64> {
+    let (_, nutation_in_obliquity) = nutation(time_since_j2000);
+    earth_obliquity(time_since_j2000) + nutation_in_obliquity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::{
+        angle::angle_to_arcsecs,
+        time::{DAY, YEAR},
+    };
+
+    #[test]
+    fn nutation_on_1987_april_10() {
+        // Meeus, Astronomical Algorithms, example 22.a
+        let time = (2446895.5 - 2451545.0) * DAY;
+        let (longitude, obliquity) = nutation(time);
+        assert!((angle_to_arcsecs(&longitude) - -3.788).abs() < 0.05);
+        assert!((angle_to_arcsecs(&obliquity) - 9.443).abs() < 0.05);
+    }
+
+    #[test]
+    fn true_obliquity_stays_within_ten_arcseconds_of_the_mean() {
+        for years in [-50., -10., 0., 3., 7., 25.] {
+            let time = years * YEAR;
+            let difference = true_obliquity(time) - earth_obliquity(time);
+            assert!(angle_to_arcsecs(&difference).abs() < 10.);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use super::earth_equatorial::ecliptic_to_equatorial;
+use crate::error::AstroUtilError;
+use astro_coords::direction::Direction;
+use simple_si_units::geometry::Angle;
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2};
+
+/*
+ * Maps of the whole sky, with longitude increasing to the left as seen from inside the celestial sphere.
+ * Both projections fill the square from -1 to 1 in x and y, so the map is twice as wide as it is high.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkyProjection {
+    Equirectangular,
+    // https://en.wikipedia.org/wiki/Hammer_projection
+    Hammer,
+}
+
+impl SkyProjection {
+    pub fn project(&self, dir: &Direction) -> (f64, f64) {
+        let longitude = dir.y().atan2(dir.x());
+        let latitude = dir.z().clamp(-1., 1.).asin();
+        match self {
+            SkyProjection::Equirectangular => (-longitude / PI, latitude / FRAC_PI_2),
+            SkyProjection::Hammer => {
+                let denominator = (1. + latitude.cos() * (longitude / 2.).cos()).sqrt();
+                let x = 2. * SQRT_2 * latitude.cos() * (longitude / 2.).sin() / denominator;
+                let y = SQRT_2 * latitude.sin() / denominator;
+                (-x / (2. * SQRT_2), y / SQRT_2)
+            }
+        }
+    }
+}
+
+/*
+ * Projects dir onto the plane that touches the unit sphere at center:
+ * https://en.wikipedia.org/wiki/Gnomonic_projection
+ * The first coordinate points east, i.e. along increasing longitude, the second one north.
+ * Both are measured in units of the sphere radius, so a direction 45° from the center has distance 1.
+ * Directions 90° or more away from the center have no projection.
+ */
+pub fn gnomonic_projection(dir: &Direction, center: &Direction) -> Option<(f64, f64)> {
+    let (east, north) = tangent_basis(center);
+    let dir = components(dir);
+    let cos_distance = dot(&dir, &components(center));
+    if cos_distance <= 0. {
+        return None;
+    }
+    Some((
+        dot(&dir, &east) / cos_distance,
+        dot(&dir, &north) / cos_distance,
+    ))
+}
+
+/*
+ * The position angle of secondary as seen from primary, measured from celestial north through east, between 0 and 360 degrees.
+ */
+pub fn position_angle(primary: &Direction, secondary: &Direction) -> Angle<f64> {
+    let (east, north) = tangent_basis_at(ecliptic_to_equatorial(primary));
+    let secondary = ecliptic_to_equatorial(secondary);
+    let angle = dot(&secondary, &east).atan2(dot(&secondary, &north));
+    Angle {
+        rad: angle.rem_euclid(2. * PI),
+    }
+}
+
+/*
+ * Returns the unit vectors pointing east and north at center.
+ * At the poles, where east is not defined, the y-axis is used instead.
+ */
+pub(crate) fn tangent_basis(center: &Direction) -> ([f64; 3], [f64; 3]) {
+    tangent_basis_at(components(center))
+}
+
+fn tangent_basis_at(center: [f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let east = [-center[1], center[0], 0.];
+    let east_length = dot(&east, &east).sqrt();
+    let east = if east_length < 1e-12 {
+        [0., 1., 0.]
+    } else {
+        [east[0] / east_length, east[1] / east_length, 0.]
+    };
+    let north = cross(&center, &east);
+    (east, north)
+}
+
+/*
+ * Maps directions onto the pixels of an image taken by a pinhole camera.
+ * The field of view is measured horizontally, and pixels are square.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionToPixel {
+    forward: [f64; 3],
+    right: [f64; 3],
+    up: [f64; 3],
+    tan_half_fov: f64,
+    width: u32,
+    height: u32,
+}
+
+impl DirectionToPixel {
+    /*
+     * The up direction only needs to be roughly perpendicular to forward; it must not be parallel to it.
+     */
+    pub fn new(
+        forward: &Direction,
+        up: &Direction,
+        field_of_view: Angle<f64>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, AstroUtilError> {
+        let forward = components(forward);
+        let right = cross(&forward, &components(up));
+        let right_length = dot(&right, &right).sqrt();
+        if right_length < 1e-12 {
+            return Err(AstroUtilError::InvalidArgument(
+                "the camera up direction is parallel to the forward direction".to_string(),
+            ));
+        }
+        let right = [
+            right[0] / right_length,
+            right[1] / right_length,
+            right[2] / right_length,
+        ];
+        let up = cross(&right, &forward);
+        Ok(Self {
+            forward,
+            right,
+            up,
+            tan_half_fov: (field_of_view.rad / 2.).tan(),
+            width,
+            height,
+        })
+    }
+
+    /*
+     * Returns the (row, column) of the pixel that dir falls onto.
+     * Row 0 is the top of the image, column 0 its left edge.
+     * Directions behind the camera or outside the image yield None.
+     */
+    pub fn project(&self, dir: &Direction) -> Option<(u32, u32)> {
+        let dir = components(dir);
+        let depth = dot(&dir, &self.forward);
+        if depth <= 0. {
+            return None;
+        }
+        let pixels_per_unit = self.width as f64 / 2. / self.tan_half_fov;
+        let col = self.width as f64 / 2. + dot(&dir, &self.right) / depth * pixels_per_unit;
+        let row = self.height as f64 / 2. - dot(&dir, &self.up) / depth * pixels_per_unit;
+        if col < 0. || row < 0. || col >= self.width as f64 || row >= self.height as f64 {
+            return None;
+        }
+        Some((row as u32, col as u32))
+    }
+}
+
+pub(crate) fn components(dir: &Direction) -> [f64; 3] {
+    [dir.x(), dir.y(), dir.z()]
+}
+
+pub(crate) fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coordinates::earth_equatorial::equatorial_to_ecliptic, tests::eq};
+
+    #[test]
+    fn sky_projections_map_poles_and_origin() {
+        for projection in [SkyProjection::Equirectangular, SkyProjection::Hammer] {
+            let (x, y) = projection.project(&Direction::X);
+            assert!(eq(x, 0.));
+            assert!(eq(y, 0.));
+            let (_, y) = projection.project(&Direction::Z);
+            assert!(eq(y, 1.));
+            let (x, _) = projection.project(&Direction::Y);
+            assert!(x < 0.);
+        }
+    }
+
+    #[test]
+    fn camera_forward_axis_maps_to_image_center() {
+        let camera = DirectionToPixel::new(
+            &Direction::X,
+            &Direction::Z,
+            Angle::from_degrees(60.),
+            640,
+            480,
+        )
+        .unwrap();
+        assert_eq!(camera.project(&Direction::X), Some((240, 320)));
+    }
+
+    #[test]
+    fn directions_behind_the_camera_are_not_mapped() {
+        let camera = DirectionToPixel::new(
+            &Direction::X,
+            &Direction::Z,
+            Angle::from_degrees(60.),
+            640,
+            480,
+        )
+        .unwrap();
+        let behind = Direction::new(-1., 0.1, 0.).unwrap();
+        assert_eq!(camera.project(&behind), None);
+    }
+
+    #[test]
+    fn up_is_at_the_top_of_the_image() {
+        let camera = DirectionToPixel::new(
+            &Direction::X,
+            &Direction::Z,
+            Angle::from_degrees(60.),
+            640,
+            480,
+        )
+        .unwrap();
+        let (row, col) = camera
+            .project(&Direction::new(1., 0., 0.1).unwrap())
+            .unwrap();
+        assert!(row < 240);
+        assert_eq!(col, 320);
+    }
+
+    #[test]
+    fn up_parallel_to_forward_is_an_invalid_argument() {
+        let camera = DirectionToPixel::new(
+            &Direction::X,
+            &Direction::X,
+            Angle::from_degrees(60.),
+            640,
+            480,
+        );
+        assert!(matches!(camera, Err(AstroUtilError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn position_angle_is_measured_from_celestial_north_through_east() {
+        let north = equatorial_to_ecliptic([1., 0., 0.01]).unwrap();
+        let east = equatorial_to_ecliptic([1., 0.01, 0.]).unwrap();
+        let south = equatorial_to_ecliptic([1., 0., -0.01]).unwrap();
+        let west = equatorial_to_ecliptic([1., -0.01, 0.]).unwrap();
+        assert!(eq(position_angle(&Direction::X, &north).to_degrees(), 0.));
+        assert!(eq(position_angle(&Direction::X, &east).to_degrees(), 90.));
+        assert!(eq(position_angle(&Direction::X, &south).to_degrees(), 180.));
+        assert!(eq(position_angle(&Direction::X, &west).to_degrees(), 270.));
+    }
+
+    #[test]
+    fn center_is_projected_to_origin() {
+        let center = Direction::new(1., 2., 3.).unwrap();
+        let (x, y) = gnomonic_projection(&center, &center).unwrap();
+        assert!(eq(x, 0.));
+        assert!(eq(y, 0.));
+    }
+
+    #[test]
+    fn directions_behind_the_center_are_not_projected() {
+        let center = Direction::new(1., 2., 3.).unwrap();
+        let behind = Direction::new(-1., -2., -3.).unwrap();
+        assert!(gnomonic_projection(&behind, &center).is_none());
+    }
+
+    #[test]
+    fn east_and_north_at_the_x_axis() {
+        let dir = Direction::new(1., 1., 0.).unwrap();
+        let (x, y) = gnomonic_projection(&dir, &Direction::X).unwrap();
+        assert!(eq(x, 1.));
+        assert!(eq(y, 0.));
+
+        let dir = Direction::new(1., 0., 1.).unwrap();
+        let (x, y) = gnomonic_projection(&dir, &Direction::X).unwrap();
+        assert!(eq(x, 0.));
+        assert!(eq(y, 1.));
+    }
+}
@@ -0,0 +1,202 @@
+use astro_coords::direction::Direction;
+use simple_si_units::geometry::Angle;
+
+use super::projection::{components, cross, dot};
+
+/*
+ * Above this cosine of the angle between two quaternions, slerp falls back to linear interpolation to avoid dividing by almost zero.
+ */
+const SLERP_LINEAR_THRESHOLD: f64 = 0.9995;
+
+/*
+ * A unit quaternion representing a rotation:
+ * https://en.wikipedia.org/wiki/Quaternions_and_spatial_rotation
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    w: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        w: 1.,
+        x: 0.,
+        y: 0.,
+        z: 0.,
+    };
+
+    /*
+     * The counterclockwise rotation by angle around axis, as seen from the tip of the axis.
+     */
+    pub fn from_axis_angle(axis: &Direction, angle: Angle<f64>) -> Self {
+        let (sin, cos) = (angle.rad / 2.).sin_cos();
+        Self {
+            w: cos,
+            x: sin * axis.x(),
+            y: sin * axis.y(),
+            z: sin * axis.z(),
+        }
+    }
+
+    /*
+     * The rotation that first applies self and then next.
+     */
+    pub fn then(&self, next: &Quat) -> Quat {
+        let (a, b) = (next, self);
+        Quat {
+            w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+            x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            y: a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            z: a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        }
+        .normalized()
+    }
+
+    pub fn inverse(&self) -> Quat {
+        Quat {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    /*
+     * Spherical linear interpolation between self (t = 0) and other (t = 1), along the shorter of the two possible arcs.
+     */
+    pub fn slerp(&self, other: &Quat, t: f64) -> Quat {
+        let mut other = *other;
+        let mut cos_angle = self.dot(&other);
+        if cos_angle < 0. {
+            other = other.scaled(-1.);
+            cos_angle = -cos_angle;
+        }
+        let (self_weight, other_weight) = if cos_angle > SLERP_LINEAR_THRESHOLD {
+            (1. - t, t)
+        } else {
+            let angle = cos_angle.acos();
+            let sin_angle = angle.sin();
+            (
+                ((1. - t) * angle).sin() / sin_angle,
+                (t * angle).sin() / sin_angle,
+            )
+        };
+        self.scaled(self_weight)
+            .added(&other.scaled(other_weight))
+            .normalized()
+    }
+
+    pub fn rotate(&self, dir: &Direction) -> Direction {
+        let v = components(dir);
+        let q = [self.x, self.y, self.z];
+        let t = cross(&q, &v).map(|component| 2. * component);
+        let q_cross_t = cross(&q, &t);
+        let rotated: [f64; 3] = std::array::from_fn(|i| v[i] + self.w * t[i] + q_cross_t[i]);
+        Direction::new(rotated[0], rotated[1], rotated[2]).unwrap_or_else(|_| dir.clone())
+    }
+
+    fn dot(&self, other: &Quat) -> f64 {
+        self.w * other.w + dot(&[self.x, self.y, self.z], &[other.x, other.y, other.z])
+    }
+
+    fn scaled(&self, factor: f64) -> Quat {
+        Quat {
+            w: factor * self.w,
+            x: factor * self.x,
+            y: factor * self.y,
+            z: factor * self.z,
+        }
+    }
+
+    fn added(&self, other: &Quat) -> Quat {
+        Quat {
+            w: self.w + other.w,
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    fn normalized(&self) -> Quat {
+        let length = self.dot(self).sqrt();
+        if length > 0. {
+            self.scaled(1. / length)
+        } else {
+            Self::IDENTITY
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coordinates::rotation::Rotation, tests::TEST_ACCURACY};
+
+    fn sample_directions() -> Vec<Direction> {
+        vec![
+            Direction::X,
+            Direction::Y,
+            Direction::Z,
+            Direction::new(1., -2., 3.).unwrap(),
+            Direction::new(-0.3, 0.1, -0.7).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn slerp_endpoints_match() {
+        let start = Quat::from_axis_angle(&Direction::Z, Angle::from_degrees(10.));
+        let end = Quat::from_axis_angle(
+            &Direction::new(1., 1., 0.).unwrap(),
+            Angle::from_degrees(120.),
+        );
+        for dir in sample_directions() {
+            let at_start = start.slerp(&end, 0.).rotate(&dir);
+            let at_end = start.slerp(&end, 1.).rotate(&dir);
+            assert!(at_start.eq_within(&start.rotate(&dir), TEST_ACCURACY));
+            assert!(at_end.eq_within(&end.rotate(&dir), TEST_ACCURACY));
+        }
+    }
+
+    #[test]
+    fn slerp_halfway_is_half_the_rotation() {
+        let end = Quat::from_axis_angle(&Direction::Z, Angle::from_degrees(90.));
+        let halfway = Quat::IDENTITY.slerp(&end, 0.5);
+        let expected = Direction::new(1., 1., 0.).unwrap();
+        assert!(halfway
+            .rotate(&Direction::X)
+            .eq_within(&expected, TEST_ACCURACY));
+    }
+
+    #[test]
+    fn quaternion_agrees_with_rotation_matrix() {
+        let axis = Direction::new(2., -1., 0.5).unwrap();
+        let angle = Angle::from_degrees(73.);
+        let quat = Quat::from_axis_angle(&axis, angle);
+        let rotation = Rotation::from_axis_angle(&axis, angle);
+        for dir in sample_directions() {
+            assert!(quat
+                .rotate(&dir)
+                .eq_within(&rotation.apply(&dir), TEST_ACCURACY));
+        }
+    }
+
+    #[test]
+    fn composition_agrees_with_rotation_matrix() {
+        let first_axis = Direction::new(0., 1., 1.).unwrap();
+        let second_axis = Direction::new(1., 0., -1.).unwrap();
+        let first_angle = Angle::from_degrees(40.);
+        let second_angle = Angle::from_degrees(-65.);
+        let quat = Quat::from_axis_angle(&first_axis, first_angle)
+            .then(&Quat::from_axis_angle(&second_axis, second_angle));
+        let rotation = Rotation::from_axis_angle(&first_axis, first_angle)
+            .then(&Rotation::from_axis_angle(&second_axis, second_angle));
+        for dir in sample_directions() {
+            assert!(quat
+                .rotate(&dir)
+                .eq_within(&rotation.apply(&dir), TEST_ACCURACY));
+        }
+    }
+}
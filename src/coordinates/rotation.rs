@@ -0,0 +1,96 @@
+use astro_coords::direction::Direction;
+use simple_si_units::geometry::Angle;
+
+use super::projection::{components, dot};
+
+/*
+ * A rotation of three-dimensional space, stored as an orthogonal matrix.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotation {
+    matrix: [[f64; 3]; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        matrix: [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+    };
+
+    /*
+     * The counterclockwise rotation by angle around axis, as seen from the tip of the axis:
+     * https://en.wikipedia.org/wiki/Rodrigues%27_rotation_formula
+     */
+    pub fn from_axis_angle(axis: &Direction, angle: Angle<f64>) -> Self {
+        let k = components(axis);
+        let (sin, cos) = angle.rad.sin_cos();
+        let cross_matrix = [[0., -k[2], k[1]], [k[2], 0., -k[0]], [-k[1], k[0], 0.]];
+        let matrix = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let identity = if i == j { 1. } else { 0. };
+                cos * identity + sin * cross_matrix[i][j] + (1. - cos) * k[i] * k[j]
+            })
+        });
+        Self { matrix }
+    }
+
+    /*
+     * The rotation that first applies self and then next.
+     */
+    pub fn then(&self, next: &Rotation) -> Rotation {
+        let matrix = std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..3).map(|k| next.matrix[i][k] * self.matrix[k][j]).sum())
+        });
+        Rotation { matrix }
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        let matrix = std::array::from_fn(|i| std::array::from_fn(|j| self.matrix[j][i]));
+        Rotation { matrix }
+    }
+
+    pub fn apply(&self, dir: &Direction) -> Direction {
+        let [x, y, z] = self.apply_to_components(components(dir));
+        Direction::new(x, y, z).unwrap_or_else(|_| dir.clone())
+    }
+
+    pub(crate) fn apply_to_components(&self, vector: [f64; 3]) -> [f64; 3] {
+        self.matrix.map(|row| dot(&row, &vector))
+    }
+
+    pub fn eq_within(&self, other: &Rotation, accuracy: f64) -> bool {
+        self.matrix
+            .iter()
+            .flatten()
+            .zip(other.matrix.iter().flatten())
+            .all(|(a, b)| (a - b).abs() < accuracy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TEST_ACCURACY;
+
+    #[test]
+    fn rotation_followed_by_its_inverse_is_identity() {
+        let axis = Direction::new(1., -2., 3.).unwrap();
+        let rotation = Rotation::from_axis_angle(&axis, Angle::from_degrees(37.));
+        let identity = rotation.then(&rotation.inverse());
+        assert!(identity.eq_within(&Rotation::IDENTITY, TEST_ACCURACY));
+    }
+
+    #[test]
+    fn quarter_turn_around_z_maps_x_to_y() {
+        let rotation = Rotation::from_axis_angle(&Direction::Z, Angle::from_degrees(90.));
+        let rotated = rotation.apply(&Direction::X);
+        assert!(rotated.eq_within(&Direction::Y, TEST_ACCURACY));
+    }
+
+    #[test]
+    fn composition_applies_rotations_in_order() {
+        let around_z = Rotation::from_axis_angle(&Direction::Z, Angle::from_degrees(90.));
+        let around_x = Rotation::from_axis_angle(&Direction::X, Angle::from_degrees(90.));
+        let rotated = around_z.then(&around_x).apply(&Direction::X);
+        assert!(rotated.eq_within(&Direction::Z, TEST_ACCURACY));
+    }
+}
@@ -3,9 +3,11 @@ use std::fmt;
 #[derive(Debug)]
 pub enum AstroUtilError {
     AstroCoordsError(astro_coords::error::AstroCoordsError),
+    #[cfg(feature = "download")]
     Connection(reqwest::Error),
     DataNotAvailable(String),
     GaiaAccess(gaia_access::error::GaiaError),
+    InvalidArgument(String),
     Io(std::io::Error),
     Json(serde_json::Error),
     MutexPoison,
@@ -18,9 +20,11 @@ impl fmt::Display for AstroUtilError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             AstroUtilError::AstroCoordsError(err) => write!(f, "AstroCoords error: {}", err),
+            #[cfg(feature = "download")]
             AstroUtilError::Connection(err) => write!(f, "Connection error: {}", err),
             AstroUtilError::DataNotAvailable(data) => write!(f, "Data {} not available", data),
             AstroUtilError::GaiaAccess(err) => write!(f, "Gaia access error: {:?}", err),
+            AstroUtilError::InvalidArgument(reason) => write!(f, "Invalid argument: {}", reason),
             AstroUtilError::Io(err) => write!(f, "I/O error: {}", err),
             AstroUtilError::Json(err) => write!(f, "JSON error: {}", err),
             AstroUtilError::MutexPoison => write!(f, "Mutex poisoned"),
@@ -35,6 +39,7 @@ impl fmt::Display for AstroUtilError {
     }
 }
 
+#[cfg(feature = "download")]
 impl From<reqwest::Error> for AstroUtilError {
     fn from(err: reqwest::Error) -> Self {
         AstroUtilError::Connection(err)
@@ -2,9 +2,11 @@
 
 pub mod astro_display;
 pub mod color;
+pub mod coordinates;
 pub mod error;
 pub mod planets;
 pub mod real_data;
+pub mod rendering;
 pub mod stars;
 pub mod units;
 
@@ -0,0 +1,93 @@
+use super::planet_data::PlanetData;
+use crate::error::AstroUtilError;
+use astro_coords::direction::Direction;
+use simple_si_units::base::{Mass, Time};
+
+/*
+ * The direction in which the target planet appears in the sky of the observing planet.
+ * Both planets are assumed to orbit the same central body.
+ */
+pub fn apparent_direction(
+    target: &PlanetData,
+    observer: &PlanetData,
+    central_body_mass: Mass<f64>,
+    time_since_epoch: Time<f64>,
+) -> Result<Direction, AstroUtilError> {
+    let target_pos = target.get_orbital_parameters().calculate_position(
+        target.get_mass(),
+        central_body_mass,
+        time_since_epoch,
+    );
+    let observer_pos = observer.get_orbital_parameters().calculate_position(
+        observer.get_mass(),
+        central_body_mass,
+        time_since_epoch,
+    );
+    let relative_position = &target_pos - &observer_pos;
+    Ok(relative_position.to_direction()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use astro_coords::cartesian::Cartesian;
+    use simple_si_units::{base::Distance, geometry::Angle};
+
+    use super::*;
+    use crate::{
+        color::srgb::sRGBColor,
+        planets::{
+            orbit_parameters::OrbitParameters, physical_parameters::PlanetPhysicalParameters,
+        },
+        units::{
+            angle::{angle_eq_within, HALF_CIRC},
+            mass::{EARTH_MASS, SOLAR_MASS},
+            tests::ANGLE_TEST_ACCURACY,
+            time::TIME_ZERO,
+        },
+    };
+
+    fn planet_on_circular_orbit(name: &str, semi_major_axis: Distance<f64>) -> PlanetData {
+        let params = PlanetPhysicalParameters::new(
+            EARTH_MASS,
+            Distance::from_km(6371.),
+            0.3,
+            sRGBColor::from_sRGB(1., 1., 1.),
+            Time::from_days(1.),
+            Direction::Z,
+        );
+        let orbit = OrbitParameters::new(
+            semi_major_axis,
+            0.,
+            Angle::from_degrees(0.),
+            Angle::from_degrees(0.),
+            Angle::from_degrees(0.),
+        );
+        PlanetData::new(name.to_string(), params, orbit)
+    }
+
+    #[test]
+    fn planet_at_opposition_appears_opposite_to_the_sun() {
+        let central_body_mass = SOLAR_MASS;
+        let observer = planet_on_circular_orbit("Inner", Distance::from_au(1.));
+        let target = planet_on_circular_orbit("Outer", Distance::from_au(5.));
+
+        let observer_pos = observer.get_orbital_parameters().calculate_position(
+            observer.get_mass(),
+            central_body_mass,
+            TIME_ZERO,
+        );
+        let sun_direction = (&Cartesian::ORIGIN - &observer_pos).to_direction().unwrap();
+        let target_direction =
+            apparent_direction(&target, &observer, central_body_mass, TIME_ZERO).unwrap();
+
+        let angle = target_direction.angle_to(&sun_direction);
+        assert!(angle_eq_within(angle, HALF_CIRC, ANGLE_TEST_ACCURACY));
+    }
+
+    #[test]
+    fn planet_does_not_appear_in_its_own_direction_relative_to_itself() {
+        let earth = planet_on_circular_orbit("Earth", Distance::from_au(1.));
+        let direction = apparent_direction(&earth, &earth, SOLAR_MASS, TIME_ZERO);
+        assert!(direction.is_err());
+    }
+}
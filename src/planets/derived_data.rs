@@ -58,7 +58,7 @@ impl DerivedPlanetData {
             None => None,
         };
 
-        let mean_synodic_day = mean_synodic_day(data.get_sideral_rotation_period(), orbital_period);
+        let mean_synodic_day = solar_day_length(data.get_sideral_rotation_period(), orbital_period);
 
         let axial_tilt = axis_tilt(data);
 
@@ -114,7 +114,7 @@ fn surface_gravity(mass: Mass<f64>, radius: Distance<f64>) -> Acceleration<f64>
     mass.to_earth_mass() / distance_to_earth_radii(&radius).powi(2) * EARTH_SURFACE_GRAVITY
 }
 
-fn escape_velocity(mass: Mass<f64>, radius: Distance<f64>) -> Velocity<f64> {
+pub(crate) fn escape_velocity(mass: Mass<f64>, radius: Distance<f64>) -> Velocity<f64> {
     let gravity = surface_gravity(mass, radius);
     Velocity {
         mps: (2. * gravity.mps2 * radius.m).sqrt(),
@@ -145,7 +145,12 @@ fn orbital_resonance(period1: Time<f64>, period2: Time<f64>) -> Option<Fraction>
     None
 }
 
-fn mean_synodic_day(siderial_day: Time<f64>, orbital_period: Time<f64>) -> Time<f64> {
+/*
+ * The time between two noons, i.e. between two passages of the central body through the meridian.
+ * A negative value means that the central body crosses the sky in the opposite direction, as on Venus.
+ * A tidally locked body has an infinite solar day, while a body that does not rotate at all has a solar day as long as its orbital period.
+ */
+pub fn solar_day_length(siderial_day: Time<f64>, orbital_period: Time<f64>) -> Time<f64> {
     1. / (1. / siderial_day - 1. / orbital_period)
 }
 
@@ -281,14 +286,21 @@ mod tests {
 
     #[test]
     fn earth_has_synodic_period_of_1_day() {
-        let synodic_day = mean_synodic_day(EARTH.siderial_rotation_period, Time::from_yr(1.));
+        let synodic_day = solar_day_length(EARTH.siderial_rotation_period, Time::from_yr(1.));
         assert!(eq(synodic_day.to_days(), 1.));
     }
 
     #[test]
     fn tidally_locked_planet_has_synodic_period_of_infinity() {
-        let synodic_day = mean_synodic_day(Time::from_days(1.), Time::from_days(1.));
-        assert!(synodic_day.s.is_infinite() || synodic_day.s.is_nan() || synodic_day.s > 1e20);
+        let synodic_day = solar_day_length(Time::from_days(1.), Time::from_days(1.));
+        assert!(synodic_day.s.is_infinite() && synodic_day.s > 0.);
+    }
+
+    #[test]
+    fn non_rotating_planet_has_solar_day_of_one_orbital_period() {
+        let orbital_period = Time::from_days(88.);
+        let solar_day = solar_day_length(Time { s: f64::INFINITY }, orbital_period);
+        assert!(eq(solar_day.to_days().abs(), orbital_period.to_days()));
     }
 
     #[test]
@@ -0,0 +1,57 @@
+use simple_si_units::base::{Distance, Mass, Time};
+
+/*
+ * Rigidity of rocky bodies in N/m^2, as used in the simplified estimate below.
+ */
+const RIGIDITY: f64 = 3e10;
+
+/*
+ * Rough estimate of the time it takes for a body to become tidally locked to the body it orbits:
+ * https://en.wikipedia.org/wiki/Tidal_locking#Timescale
+ * The initial rotation is assumed to be fast, and the result is only accurate to an order of magnitude or so.
+ */
+pub fn tidal_locking_timescale(
+    planet_mass: Mass<f64>,
+    planet_radius: Distance<f64>,
+    orbit_semi_major_axis: Distance<f64>,
+    star_mass: Mass<f64>,
+) -> Time<f64> {
+    let a = orbit_semi_major_axis.m;
+    let years = 6. * a.powi(6) * planet_radius.m * RIGIDITY
+        / (planet_mass.kg * star_mass.kg.powi(2))
+        * 1e10;
+    Time::from_yr(years)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        real_data::planets::{EARTH, MOON},
+        units::mass::SOLAR_MASS,
+    };
+
+    #[test]
+    fn close_planet_locks_faster_than_distant_planet() {
+        let close = tidal_locking_timescale(
+            EARTH.mass,
+            EARTH.radius,
+            Distance::from_au(0.05),
+            SOLAR_MASS,
+        );
+        let distant =
+            tidal_locking_timescale(EARTH.mass, EARTH.radius, Distance::from_au(1.), SOLAR_MASS);
+        assert!(close.to_yr() * 1e6 < distant.to_yr());
+    }
+
+    #[test]
+    fn moon_locks_to_earth_quickly() {
+        let timescale = tidal_locking_timescale(
+            MOON.mass,
+            MOON.radius,
+            MOON.orbit.get_semi_major_axis(),
+            EARTH.mass,
+        );
+        assert!(timescale.to_yr() < 1e8);
+    }
+}
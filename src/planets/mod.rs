@@ -1,9 +1,14 @@
+pub mod apparent_direction;
 pub mod derived_data;
+pub mod dynamics;
 pub mod kepler_orbit;
+pub mod moon;
 pub mod orbit_parameters;
 pub mod physical_parameters;
 pub mod planet_brightness;
 pub mod planet_data;
+pub mod planetary_system;
 pub mod random_planets;
 pub mod real_data;
+pub mod sun;
 pub mod surface_normal;
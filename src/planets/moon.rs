@@ -0,0 +1,99 @@
+use astro_coords::direction::Direction;
+use simple_si_units::geometry::Angle;
+
+use crate::{coordinates::projection::position_angle, units::angle::normalized_angle};
+
+/*
+ * Within this many degrees of full, quarter and new moon, the phase is named after that point.
+ */
+const HALF_PHASE_WIDTH_IN_DEGREES: f64 = 22.5;
+
+/*
+ * The name of the phase of the Moon, given its phase angle and whether the illuminated part is growing.
+ * The phase angle is the angle between the directions from the Moon to the Sun and to the observer, so it is 0 at full moon and 180 degrees at new moon.
+ */
+pub fn moon_phase_name(phase_angle: Angle<f64>, waxing: bool) -> &'static str {
+    let degrees = normalized_angle(phase_angle).to_degrees().abs();
+    if degrees < HALF_PHASE_WIDTH_IN_DEGREES {
+        "Full Moon"
+    } else if degrees > 180. - HALF_PHASE_WIDTH_IN_DEGREES {
+        "New Moon"
+    } else if (degrees - 90.).abs() <= HALF_PHASE_WIDTH_IN_DEGREES {
+        if waxing {
+            "First Quarter"
+        } else {
+            "Last Quarter"
+        }
+    } else if degrees < 90. {
+        if waxing {
+            "Waxing Gibbous"
+        } else {
+            "Waning Gibbous"
+        }
+    } else if waxing {
+        "Waxing Crescent"
+    } else {
+        "Waning Crescent"
+    }
+}
+
+/*
+ * The position angle of the midpoint of the illuminated limb of a body, measured from celestial north through east.
+ * The bright limb faces the Sun, so this is the position angle of the Sun as seen from the body's place on the sky:
+ * https://en.wikipedia.org/wiki/Position_angle
+ */
+pub fn bright_limb_angle(sun_dir: &Direction, body_dir: &Direction) -> Angle<f64> {
+    position_angle(body_dir, sun_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::earth_equatorial::equatorial_to_ecliptic, tests::eq, units::angle::ANGLE_ZERO,
+    };
+
+    #[test]
+    fn full_and_new_moon() {
+        for waxing in [true, false] {
+            assert_eq!(moon_phase_name(ANGLE_ZERO, waxing), "Full Moon");
+            assert_eq!(
+                moon_phase_name(Angle::from_degrees(180.), waxing),
+                "New Moon"
+            );
+            assert_eq!(
+                moon_phase_name(Angle::from_degrees(-180.), waxing),
+                "New Moon"
+            );
+        }
+    }
+
+    #[test]
+    fn phases_between_full_and_new_moon() {
+        assert_eq!(
+            moon_phase_name(Angle::from_degrees(45.), true),
+            "Waxing Gibbous"
+        );
+        assert_eq!(
+            moon_phase_name(Angle::from_degrees(90.), false),
+            "Last Quarter"
+        );
+        assert_eq!(
+            moon_phase_name(Angle::from_degrees(135.), true),
+            "Waxing Crescent"
+        );
+        assert_eq!(
+            moon_phase_name(Angle::from_degrees(135.), false),
+            "Waning Crescent"
+        );
+    }
+
+    #[test]
+    fn bright_limb_points_towards_the_sun() {
+        let body = Direction::X;
+        let sun_above = equatorial_to_ecliptic([1., 0., 0.5]).unwrap();
+        let sun_east = equatorial_to_ecliptic([0., 1., 0.]).unwrap();
+        assert!(eq(bright_limb_angle(&sun_above, &body).to_degrees(), 0.));
+        assert!(eq(bright_limb_angle(&sun_east, &body).to_degrees(), 90.));
+    }
+}
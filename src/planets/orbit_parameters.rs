@@ -9,6 +9,7 @@ use crate::planets::kepler_orbit::{
     eccentric_anomaly, mean_anomaly, orbital_period, position_relative_to_central_body,
     true_anomaly,
 };
+use crate::units::mass::MASS_ZERO;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrbitParameters {
@@ -56,13 +57,17 @@ impl OrbitParameters {
         self.argument_of_periapsis
     }
 
+    pub fn orbital_period(&self, body_mass: Mass<f64>, central_body_mass: Mass<f64>) -> Time<f64> {
+        orbital_period(self.semi_major_axis, body_mass, central_body_mass)
+    }
+
     pub fn calculate_position(
         &self,
         body_mass: Mass<f64>,
         central_body_mass: Mass<f64>,
         time: Time<f64>,
     ) -> Cartesian {
-        let orbital_period = orbital_period(self.semi_major_axis, body_mass, central_body_mass);
+        let orbital_period = self.orbital_period(body_mass, central_body_mass);
         let mean_anomaly = mean_anomaly(orbital_period, time);
         let eccentric_anomaly = eccentric_anomaly(mean_anomaly, self.eccentricity);
         let true_anomaly = true_anomaly(eccentric_anomaly, self.eccentricity);
@@ -87,11 +92,40 @@ impl OrbitParameters {
     }
 }
 
+/*
+ * The time between two successive alignments of two bodies orbiting the same central body, e.g. between two oppositions of Mars as seen from Earth.
+ * The masses of the orbiting bodies are neglected.
+ */
+pub fn synodic_period(
+    orbit_a: &OrbitParameters,
+    orbit_b: &OrbitParameters,
+    central_body_mass: Mass<f64>,
+) -> Time<f64> {
+    let period_a = orbit_a.orbital_period(MASS_ZERO, central_body_mass);
+    let period_b = orbit_b.orbital_period(MASS_ZERO, central_body_mass);
+    Time {
+        s: 1. / (1. / period_a.s - 1. / period_b.s).abs(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use simple_si_units::geometry::Angle;
 
-    use crate::{real_data::planets::*, units::angle::angle_eq_within};
+    use super::*;
+    use crate::{
+        real_data::planets::*,
+        tests::eq_within,
+        units::{angle::angle_eq_within, mass::SOLAR_MASS},
+    };
+
+    #[test]
+    fn synodic_period_of_earth_and_mars() {
+        let period = synodic_period(&EARTH.orbit, &MARS.orbit, SOLAR_MASS);
+        assert!(eq_within(period.to_days(), 780., 2.));
+        let reversed = synodic_period(&MARS.orbit, &EARTH.orbit, SOLAR_MASS);
+        assert!(eq_within(reversed.to_days(), period.to_days(), 1e-6));
+    }
 
     const TILT_ACCURACY: Angle<f64> = Angle { rad: 2e-3 };
 
@@ -24,7 +24,7 @@ use crate::{
 /*
  * https://www.physicsforums.com/threads/illuminated-fraction-of-the-moon.515983/
  */
-fn illuminated_fraction(reflection_angle: &Angle<f64>) -> f64 {
+pub(crate) fn illuminated_fraction(reflection_angle: &Angle<f64>) -> f64 {
     (1. + reflection_angle.rad.cos()) / 2.
 }
 
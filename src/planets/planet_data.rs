@@ -2,14 +2,19 @@ use super::{orbit_parameters::OrbitParameters, physical_parameters::PlanetPhysic
 use crate::{
     color::srgb::sRGBColor,
     error::AstroUtilError,
-    planets::planet_brightness::planet_brightness,
+    planets::{
+        derived_data::escape_velocity,
+        planet_brightness::{illuminated_fraction, planet_brightness},
+        surface_normal::surface_normal_at_time,
+    },
     stars::{appearance::StarAppearance, data::StarData},
 };
-use astro_coords::{cartesian::Cartesian, direction::Direction};
+use astro_coords::{cartesian::Cartesian, direction::Direction, equatorial::Equatorial};
 use serde::{Deserialize, Serialize};
 use simple_si_units::{
     base::{Distance, Mass, Time},
     geometry::Angle,
+    mechanical::Velocity,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +123,40 @@ impl PlanetData {
         self.params.rotation_axis = rotation_axis;
     }
 
+    pub fn escape_velocity(&self) -> Velocity<f64> {
+        escape_velocity(self.params.mass, self.params.radius)
+    }
+
+    pub fn orbital_period(&self, central_body_mass: Mass<f64>) -> Time<f64> {
+        self.orbital_parameters
+            .orbital_period(self.params.mass, central_body_mass)
+    }
+
+    /*
+     * The direction in which the surface at the observer's position faces, using the planet's rotation period.
+     */
+    pub fn surface_normal_at_time(
+        &self,
+        observer: Equatorial,
+        angle_at_epoch: Angle<f64>,
+        time_since_epoch: Time<f64>,
+    ) -> Direction {
+        surface_normal_at_time(
+            observer,
+            angle_at_epoch,
+            time_since_epoch,
+            self.params.sideral_rotation_period,
+        )
+    }
+
+    /*
+     * The color of the planet dimmed by the fraction of its disk that is lit, for display purposes.
+     * The phase angle is the angle between the directions from the planet to the star and to the observer.
+     */
+    pub fn color_at_phase(&self, phase_angle: Angle<f64>) -> sRGBColor {
+        &self.params.color * illuminated_fraction(&phase_angle)
+    }
+
     pub fn to_star_appearance(
         &self,
         central_body: &StarData,
@@ -145,3 +184,70 @@ impl PlanetData {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        real_data::planets::EARTH,
+        tests::{eq_within, TEST_ACCURACY},
+        units::{angle::ANGLE_ZERO, mass::SOLAR_MASS, time::HOUR},
+    };
+    use astro_coords::spherical::Spherical;
+
+    #[test]
+    fn escape_velocity_of_earth() {
+        let earth = EARTH.to_planet_data();
+        assert!(eq_within(earth.escape_velocity().to_kmps(), 11.2, 0.25));
+    }
+
+    #[test]
+    fn orbital_period_of_earth() {
+        let earth = EARTH.to_planet_data();
+        assert!(eq_within(
+            earth.orbital_period(SOLAR_MASS).to_yr(),
+            1.,
+            1e-3
+        ));
+    }
+
+    #[test]
+    fn surface_normal_uses_the_rotation_period_of_the_planet() {
+        let earth = EARTH.to_planet_data();
+        let observer = || {
+            Equatorial::new(
+                Spherical::new(Angle::from_degrees(30.), Angle::from_degrees(50.)),
+                earth.get_rotation_axis().clone(),
+            )
+        };
+        let angle_at_epoch = Angle::from_degrees(10.);
+        let time = 7. * HOUR;
+
+        let actual = earth.surface_normal_at_time(observer(), angle_at_epoch, time);
+        let expected = surface_normal_at_time(
+            observer(),
+            angle_at_epoch,
+            time,
+            earth.get_sideral_rotation_period(),
+        );
+
+        assert!(actual.eq_within(&expected, TEST_ACCURACY));
+    }
+
+    #[test]
+    fn fully_lit_planet_has_its_base_color() {
+        let mut earth = EARTH.to_planet_data();
+        earth.set_color(sRGBColor::from_sRGB(0.8, 0.6, 0.4));
+        assert_eq!(earth.color_at_phase(ANGLE_ZERO), *earth.get_color());
+    }
+
+    #[test]
+    fn crescent_planet_is_dimmer_than_its_base_color() {
+        let mut earth = EARTH.to_planet_data();
+        earth.set_color(sRGBColor::from_sRGB(0.8, 0.6, 0.4));
+        let crescent = earth.color_at_phase(Angle::from_degrees(160.));
+        let expected = sRGBColor::from_sRGB(0.8, 0.6, 0.4);
+        assert!(crescent < expected);
+        assert!(crescent > sRGBColor::from_sRGB(0., 0., 0.));
+    }
+}
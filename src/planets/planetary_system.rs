@@ -0,0 +1,128 @@
+use super::planet_data::PlanetData;
+use crate::{
+    error::AstroUtilError,
+    stars::{appearance::StarAppearance, data::StarData},
+};
+use astro_coords::cartesian::Cartesian;
+use serde::{Deserialize, Serialize};
+use simple_si_units::base::{Mass, Time};
+
+/*
+ * A central star with planets orbiting it.
+ * Positions are given relative to the central body, which sits at the origin.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetarySystem {
+    central_body: StarData,
+    planets: Vec<PlanetData>,
+}
+
+impl PlanetarySystem {
+    pub fn new(central_body: StarData, planets: Vec<PlanetData>) -> Self {
+        PlanetarySystem {
+            central_body,
+            planets,
+        }
+    }
+
+    pub fn get_central_body(&self) -> &StarData {
+        &self.central_body
+    }
+
+    pub fn get_planets(&self) -> &Vec<PlanetData> {
+        &self.planets
+    }
+
+    pub fn add_planet(&mut self, planet: PlanetData) {
+        self.planets.push(planet);
+    }
+
+    fn central_body_mass(&self) -> Result<Mass<f64>, AstroUtilError> {
+        self.central_body.get_mass_at_epoch().ok_or_else(|| {
+            AstroUtilError::DataNotAvailable("Central body is missing mass.".to_string())
+        })
+    }
+
+    pub fn planet_positions(
+        &self,
+        time_since_epoch: Time<f64>,
+    ) -> Result<Vec<Cartesian>, AstroUtilError> {
+        let central_body_mass = self.central_body_mass()?;
+        let positions = self
+            .planets
+            .iter()
+            .map(|planet| {
+                planet.get_orbital_parameters().calculate_position(
+                    planet.get_mass(),
+                    central_body_mass,
+                    time_since_epoch,
+                )
+            })
+            .collect();
+        Ok(positions)
+    }
+
+    pub fn planet_appearances(
+        &self,
+        observer_position: &Cartesian,
+        time_since_epoch: Time<f64>,
+    ) -> Result<Vec<StarAppearance>, AstroUtilError> {
+        let positions = self.planet_positions(time_since_epoch)?;
+        self.planets
+            .iter()
+            .zip(positions.iter())
+            .map(|(planet, planet_pos)| {
+                planet.to_star_appearance(
+                    &self.central_body,
+                    planet_pos,
+                    observer_position,
+                    time_since_epoch,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use astro_coords::direction::Direction;
+    use simple_si_units::base::Distance;
+
+    use super::*;
+    use crate::{
+        real_data::{planets::EARTH, stars::SUN},
+        units::time::TIME_ZERO,
+    };
+
+    #[test]
+    fn planet_of_one_planet_system_has_nonzero_brightness() {
+        let system = PlanetarySystem::new(SUN.to_star_data(), vec![EARTH.to_planet_data()]);
+        let observer_position = Direction::Z.to_cartesian(Distance::from_au(10.));
+        let appearances = system
+            .planet_appearances(&observer_position, TIME_ZERO)
+            .unwrap();
+        assert_eq!(appearances.len(), 1);
+        assert!(appearances[0].get_illuminance().lux > 0.);
+    }
+
+    #[test]
+    fn planet_brightness_changes_over_time() {
+        let system = PlanetarySystem::new(SUN.to_star_data(), vec![EARTH.to_planet_data()]);
+        let observer_position = Direction::X.to_cartesian(Distance::from_au(10.));
+        let now = system
+            .planet_appearances(&observer_position, TIME_ZERO)
+            .unwrap();
+        let later = system
+            .planet_appearances(&observer_position, Time::from_days(100.))
+            .unwrap();
+        assert!(now[0].get_illuminance() != later[0].get_illuminance());
+    }
+
+    #[test]
+    fn central_body_without_mass_is_an_error() {
+        let mut sun = SUN.to_star_data();
+        sun.set_mass_at_epoch(None);
+        let system = PlanetarySystem::new(sun, vec![EARTH.to_planet_data()]);
+        assert!(system.planet_positions(TIME_ZERO).is_err());
+    }
+}
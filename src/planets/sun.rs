@@ -0,0 +1,89 @@
+use simple_si_units::{base::Time, geometry::Angle};
+
+use crate::{coordinates::earth_equatorial::earth_obliquity, units::time::DAY};
+
+/*
+ * The apparent ecliptic longitude of the Sun as seen from Earth, at the given time since J2000, between 0 and 360 degrees.
+ * This is the low precision series of the Astronomical Almanac, accurate to about 0.01 degrees between 1950 and 2050:
+ * https://en.wikipedia.org/wiki/Position_of_the_Sun#Ecliptic_coordinates
+ */
+pub fn sun_ecliptic_longitude(time_since_j2000: Time<f64>) -> Angle<f64> {
+    let n = time_since_j2000 / DAY;
+    let mean_longitude = sun_mean_longitude_in_degrees(n);
+    let mean_anomaly = (357.528 + 0.9856003 * n).to_radians();
+    let longitude = mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2. * mean_anomaly).sin();
+    Angle::from_degrees(longitude.rem_euclid(360.))
+}
+
+/*
+ * How far a sundial runs ahead of a clock showing mean solar time, at the given time since J2000.
+ * It is the difference between the Sun's mean longitude and its right ascension:
+ * https://en.wikipedia.org/wiki/Equation_of_time
+ */
+pub fn equation_of_time(time_since_j2000: Time<f64>) -> Time<f64> {
+    let mean_longitude = sun_mean_longitude_in_degrees(time_since_j2000 / DAY);
+    let longitude = sun_ecliptic_longitude(time_since_j2000).rad;
+    let cos_obl = earth_obliquity(time_since_j2000).rad.cos();
+    let right_ascension = (cos_obl * longitude.sin())
+        .atan2(longitude.cos())
+        .to_degrees();
+    let difference = (mean_longitude - right_ascension + 180.).rem_euclid(360.) - 180.;
+    difference / 360. * DAY
+}
+
+fn sun_mean_longitude_in_degrees(days_since_j2000: f64) -> f64 {
+    280.460 + 0.9856474 * days_since_j2000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::angle::{angle_eq_within, DEGREE};
+
+    #[test]
+    fn sun_is_at_vernal_point_during_march_equinox() {
+        // 2000-03-20 07:35 UT
+        let time = 78.816 * DAY;
+        let longitude = sun_ecliptic_longitude(time);
+        assert!(angle_eq_within(
+            longitude,
+            Angle::from_degrees(0.),
+            0.05 * DEGREE
+        ));
+    }
+
+    #[test]
+    fn sun_is_opposite_the_vernal_point_during_september_equinox() {
+        // 2000-09-22 17:28 UT
+        let time = 265.228 * DAY;
+        let longitude = sun_ecliptic_longitude(time);
+        assert!(angle_eq_within(
+            longitude,
+            Angle::from_degrees(180.),
+            0.05 * DEGREE
+        ));
+    }
+
+    #[test]
+    fn equation_of_time_stays_within_sixteen_and_a_half_minutes() {
+        for day in 0..366 {
+            let minutes = equation_of_time(day as f64 * DAY).to_min();
+            assert!(minutes.abs() < 16.5);
+        }
+    }
+
+    #[test]
+    fn equation_of_time_vanishes_four_times_a_year() {
+        // Around April 15, June 13, September 1 and December 25
+        let expected_crossings = [104., 163., 243., 358.];
+        let minutes = |day: f64| equation_of_time(day * DAY).to_min();
+        let crossings = (0..365)
+            .map(|day| day as f64)
+            .filter(|day| minutes(*day) * minutes(day + 1.) < 0.)
+            .collect::<Vec<f64>>();
+        assert_eq!(crossings.len(), expected_crossings.len());
+        for (actual, expected) in crossings.iter().zip(expected_crossings) {
+            assert!((actual - expected).abs() <= 2.);
+        }
+    }
+}
@@ -1,7 +1,17 @@
-use astro_coords::{direction::Direction, equatorial::Equatorial};
-use simple_si_units::{base::Time, geometry::Angle};
-
-use crate::units::angle::FULL_CIRC;
+use astro_coords::{
+    cartesian::Cartesian, direction::Direction, equatorial::Equatorial, spherical::Spherical,
+};
+use simple_si_units::{
+    base::{Mass, Time},
+    geometry::Angle,
+};
+
+use super::planet_data::PlanetData;
+use crate::{
+    coordinates::projection::{components, dot},
+    error::AstroUtilError,
+    units::angle::{ANGLE_ZERO, FULL_CIRC, QUARTER_CIRC},
+};
 
 pub fn surface_normal_at_time(
     mut observer: Equatorial,
@@ -17,13 +27,104 @@ pub fn surface_normal_at_time(
     observer.to_direction()
 }
 
+/*
+ * The latitude and longitude of the point on the planet's surface where the central body stands in the zenith.
+ * Longitudes are measured like those of the observers passed to surface_normal_at_time with the same angle at epoch.
+ */
+pub fn subsolar_point(
+    planet: &PlanetData,
+    central_body_mass: Mass<f64>,
+    angle_at_epoch: Angle<f64>,
+    time_since_epoch: Time<f64>,
+) -> Result<(Angle<f64>, Angle<f64>), AstroUtilError> {
+    let to_central_body = components(&direction_to_central_body(
+        planet,
+        central_body_mass,
+        time_since_epoch,
+    )?);
+    let axis = planet.get_rotation_axis();
+    let surface_normal = |longitude: Angle<f64>| {
+        let observer = Equatorial::new(Spherical::new(longitude, ANGLE_ZERO), axis.clone());
+        components(&surface_normal_at_time(
+            observer,
+            angle_at_epoch,
+            time_since_epoch,
+            planet.get_sideral_rotation_period(),
+        ))
+    };
+    let longitude_zero = surface_normal(ANGLE_ZERO);
+    let longitude_quarter = surface_normal(QUARTER_CIRC);
+    let longitude = dot(&to_central_body, &longitude_quarter)
+        .atan2(dot(&to_central_body, &longitude_zero))
+        .rem_euclid(FULL_CIRC.rad);
+    let latitude = declination_of(&to_central_body, axis);
+    Ok((latitude, Angle { rad: longitude }))
+}
+
+/*
+ * The angle between the central body and the planet's equatorial plane, which determines the seasons.
+ * It equals the latitude of the subsolar point.
+ */
+pub fn solar_declination(
+    planet: &PlanetData,
+    central_body_mass: Mass<f64>,
+    time_since_epoch: Time<f64>,
+) -> Result<Angle<f64>, AstroUtilError> {
+    let to_central_body = components(&direction_to_central_body(
+        planet,
+        central_body_mass,
+        time_since_epoch,
+    )?);
+    Ok(declination_of(&to_central_body, planet.get_rotation_axis()))
+}
+
+fn declination_of(dir: &[f64; 3], axis: &Direction) -> Angle<f64> {
+    Angle {
+        rad: dot(dir, &components(axis)).clamp(-1., 1.).asin(),
+    }
+}
+
+/*
+ * Whether the central body is above the horizon of the given point on the planet's surface.
+ */
+pub fn is_illuminated(
+    planet: &PlanetData,
+    surface_point: Equatorial,
+    central_body_mass: Mass<f64>,
+    angle_at_epoch: Angle<f64>,
+    time_since_epoch: Time<f64>,
+) -> Result<bool, AstroUtilError> {
+    let to_central_body = direction_to_central_body(planet, central_body_mass, time_since_epoch)?;
+    let normal = surface_normal_at_time(
+        surface_point,
+        angle_at_epoch,
+        time_since_epoch,
+        planet.get_sideral_rotation_period(),
+    );
+    Ok(dot(&components(&normal), &components(&to_central_body)) > 0.)
+}
+
+fn direction_to_central_body(
+    planet: &PlanetData,
+    central_body_mass: Mass<f64>,
+    time_since_epoch: Time<f64>,
+) -> Result<Direction, AstroUtilError> {
+    let position = planet.get_orbital_parameters().calculate_position(
+        planet.get_mass(),
+        central_body_mass,
+        time_since_epoch,
+    );
+    Ok((&Cartesian::ORIGIN - &position).to_direction()?)
+}
+
 #[cfg(test)]
 mod tests {
     use astro_coords::spherical::Spherical;
 
     use crate::{
+        real_data::planets::EARTH,
         tests::TEST_ACCURACY,
-        units::{angle::ANGLE_ZERO, time::TIME_ZERO},
+        units::{mass::SOLAR_MASS, time::TIME_ZERO},
     };
 
     use super::*;
@@ -219,4 +320,65 @@ mod tests {
         println!("expected: {},\n actual: {}", expected, actual);
         assert!(actual.eq_within(&expected, TEST_ACCURACY));
     }
+
+    #[test]
+    fn subsolar_point_of_untilted_circular_orbit_stays_on_the_equator() {
+        let mut planet = EARTH.to_planet_data();
+        planet.set_rotation_axis(Direction::Z);
+        planet.set_eccentricity(0.);
+        planet.set_inclination(ANGLE_ZERO);
+        for days in [0., 17., 91.3, 180., 250.5, 333.] {
+            let time = Time::from_days(days);
+            let (latitude, longitude) =
+                subsolar_point(&planet, SOLAR_MASS, ANGLE_ZERO, time).unwrap();
+            assert!(latitude.rad.abs() < TEST_ACCURACY);
+            assert!((0. ..FULL_CIRC.rad).contains(&longitude.rad));
+        }
+    }
+
+    #[test]
+    fn subsolar_point_is_illuminated_and_its_antipode_is_not() {
+        let planet = EARTH.to_planet_data();
+        let axis = planet.get_rotation_axis().clone();
+        let angle_at_epoch = Angle::from_degrees(12.);
+        let time = Time::from_days(40.3);
+        let (latitude, longitude) =
+            subsolar_point(&planet, SOLAR_MASS, angle_at_epoch, time).unwrap();
+        let facing = Equatorial::new(Spherical::new(longitude, latitude), axis.clone());
+        let antipode = Equatorial::new(
+            Spherical::new(
+                longitude + Angle::from_degrees(180.),
+                Angle { rad: -latitude.rad },
+            ),
+            axis,
+        );
+
+        assert!(is_illuminated(&planet, facing, SOLAR_MASS, angle_at_epoch, time).unwrap());
+        assert!(!is_illuminated(&planet, antipode, SOLAR_MASS, angle_at_epoch, time).unwrap());
+    }
+
+    #[test]
+    fn solar_declination_of_earth_swings_between_the_tropics() {
+        let planet = EARTH.to_planet_data();
+        let declinations = (0..366)
+            .map(|day| {
+                solar_declination(&planet, SOLAR_MASS, Time::from_days(day as f64))
+                    .unwrap()
+                    .to_degrees()
+            })
+            .collect::<Vec<f64>>();
+        let (day_of_max, max) = declinations
+            .iter()
+            .enumerate()
+            .fold((0, f64::MIN), |a, (i, &d)| if d > a.1 { (i, d) } else { a });
+        let (day_of_min, min) = declinations
+            .iter()
+            .enumerate()
+            .fold((0, f64::MAX), |a, (i, &d)| if d < a.1 { (i, d) } else { a });
+
+        assert!((max - 23.44).abs() < 0.5);
+        assert!((min + 23.44).abs() < 0.5);
+        let days_between_solstices = (day_of_max as f64 - day_of_min as f64).abs();
+        assert!((days_between_solstices - 182.6).abs() < 6.);
+    }
 }
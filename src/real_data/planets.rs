@@ -409,3 +409,45 @@ pub const MOON: RealData = RealData {
         },
     ),
 };
+
+/*
+ * All bodies that orbit the Sun directly, ordered by their distance to it.
+ * The Moon is not contained, because it orbits Earth.
+ */
+pub fn get_all_planets() -> Vec<RealData> {
+    vec![
+        MERCURY, VENUS, EARTH, MARS, CERES, JUPITER, SATURN, URANUS, NEPTUNE, PLUTO,
+    ]
+}
+
+pub fn get_planet_by_name(name: &str) -> Option<RealData> {
+    get_all_planets()
+        .into_iter()
+        .find(|planet| planet.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_planets_contain_earth_and_jupiter_but_not_the_moon() {
+        let planets = get_all_planets();
+        assert_eq!(planets.len(), 10);
+        let names = planets
+            .iter()
+            .map(|planet| planet.name)
+            .collect::<Vec<&str>>();
+        assert!(names.contains(&"Earth"));
+        assert!(names.contains(&"Jupiter"));
+        assert!(!names.contains(&MOON.name));
+    }
+
+    #[test]
+    fn planets_are_found_by_name() {
+        let saturn = get_planet_by_name("Saturn").unwrap();
+        assert_eq!(saturn.name, SATURN.name);
+        assert_eq!(saturn.mass, SATURN.mass);
+        assert!(get_planet_by_name("Schnuffelpuff").is_none());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    coordinates::projection::DirectionToPixel,
+    error::AstroUtilError,
+    stars::{appearance::StarAppearance, data::StarData},
+};
+use astro_coords::{cartesian::Cartesian, direction::Direction};
+use simple_si_units::{base::Time, geometry::Angle};
+
+/*
+ * A pinhole camera looking at the sky.
+ * The appearances passed to it are expected to be seen from the camera position, as returned by appearances_of.
+ */
+#[derive(Debug, Clone)]
+pub struct Camera {
+    position: Cartesian,
+    look_direction: Direction,
+    up: Direction,
+    field_of_view: Angle<f64>,
+    width: u32,
+    height: u32,
+    projection: DirectionToPixel,
+}
+
+impl Camera {
+    pub fn new(
+        position: Cartesian,
+        look_direction: Direction,
+        up: Direction,
+        field_of_view: Angle<f64>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, AstroUtilError> {
+        let projection = DirectionToPixel::new(&look_direction, &up, field_of_view, width, height)?;
+        Ok(Self {
+            position,
+            look_direction,
+            up,
+            field_of_view,
+            width,
+            height,
+            projection,
+        })
+    }
+
+    pub fn get_position(&self) -> &Cartesian {
+        &self.position
+    }
+
+    pub fn get_look_direction(&self) -> &Direction {
+        &self.look_direction
+    }
+
+    pub fn get_up(&self) -> &Direction {
+        &self.up
+    }
+
+    pub fn get_field_of_view(&self) -> &Angle<f64> {
+        &self.field_of_view
+    }
+
+    pub fn get_resolution(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /*
+     * Returns the (row, column) of the pixel that dir falls onto, if it is in the picture.
+     */
+    pub fn pixel_of(&self, dir: &Direction) -> Option<(u32, u32)> {
+        self.projection.project(dir)
+    }
+
+    /*
+     * The appearances of the stars as seen from the camera position.
+     */
+    pub fn appearances_of(
+        &self,
+        stars: &[StarData],
+        time_since_epoch: Time<f64>,
+    ) -> Vec<StarAppearance> {
+        stars
+            .iter()
+            .map(|star| star.to_star_appearance_from(&self.position, time_since_epoch))
+            .collect()
+    }
+
+    /*
+     * Returns the pixel (row, column) of each star in the picture, together with the star itself.
+     */
+    pub fn visible_stars<'a>(
+        &self,
+        appearances: &'a [StarAppearance],
+    ) -> Vec<(u32, u32, &'a StarAppearance)> {
+        appearances
+            .iter()
+            .filter_map(|appearance| {
+                let (row, col) = self.pixel_of(&appearance.pos.spherical.to_direction())?;
+                Some((row, col, appearance))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::srgb::sRGBColor,
+        stars::{evolution::StarDataEvolution, physical_parameters::StarPhysicalParameters},
+        units::{
+            angle::{ANGLE_ZERO, HALF_CIRC},
+            luminous_intensity::SOLAR_LUMINOUS_INTENSITY,
+            time::TIME_ZERO,
+        },
+    };
+    use astro_coords::{ecliptic::Ecliptic, spherical::Spherical};
+    use simple_si_units::{
+        base::{Distance, Temperature},
+        electromagnetic::Illuminance,
+    };
+
+    fn star_at(pos: Ecliptic) -> StarAppearance {
+        StarAppearance::new(
+            "Schnuffelpuff".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+            pos,
+            TIME_ZERO,
+        )
+    }
+
+    #[test]
+    fn star_ahead_is_visible_in_the_center_and_star_behind_is_not() {
+        let camera = Camera::new(
+            Cartesian::ORIGIN,
+            Direction::X,
+            Direction::Z,
+            Angle::from_degrees(60.),
+            640,
+            480,
+        )
+        .unwrap();
+        let ahead = star_at(Ecliptic::X_DIRECTION);
+        let behind = star_at(Spherical::new(HALF_CIRC, ANGLE_ZERO).to_ecliptic());
+        let stars = vec![ahead.clone(), behind];
+
+        let visible = camera.visible_stars(&stars);
+
+        assert_eq!(visible.len(), 1);
+        let (row, col, star) = visible[0];
+        assert!((239..=240).contains(&row));
+        assert!((319..=320).contains(&col));
+        assert_eq!(star, &ahead);
+    }
+
+    #[test]
+    fn stars_are_seen_from_the_camera_position() {
+        let camera = Camera::new(
+            Direction::X.to_cartesian(Distance::from_lyr(2.)),
+            Direction::X,
+            Direction::Z,
+            Angle::from_degrees(60.),
+            640,
+            480,
+        )
+        .unwrap();
+        let star = StarData::new(
+            "Schnuffelpuff".to_string(),
+            None,
+            StarPhysicalParameters::new(
+                None,
+                None,
+                SOLAR_LUMINOUS_INTENSITY,
+                Temperature::from_K(5778.),
+            ),
+            Direction::X.to_cartesian(Distance::from_lyr(1.)),
+            StarDataEvolution::NONE,
+        );
+
+        let from_origin = vec![star.to_star_appearance(TIME_ZERO)];
+        assert_eq!(camera.visible_stars(&from_origin).len(), 1);
+
+        let from_camera = camera.appearances_of(&[star], TIME_ZERO);
+        assert!(camera.visible_stars(&from_camera).is_empty());
+    }
+}
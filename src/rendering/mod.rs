@@ -0,0 +1,3 @@
+pub mod camera;
+pub mod render;
+pub mod tone_map;
@@ -0,0 +1,225 @@
+use super::{camera::Camera, tone_map::ToneMap};
+use crate::{stars::appearance::StarAppearance, units::illuminance::IRRADIANCE_ZERO};
+use simple_si_units::electromagnetic::Illuminance;
+use std::{collections::HashMap, f64::consts::PI};
+
+/*
+ * Beyond this many standard deviations, the point spread function is cut off.
+ */
+const PSF_CUTOFF_IN_SIGMA: f64 = 4.;
+
+/*
+ * Renders the illuminance in lux that falls onto each pixel, stored row by row.
+ * Each star is spread over the neighbouring pixels by a normalised Gaussian with a standard deviation of psf_sigma pixels.
+ * For psf_sigma of zero or less, all light of a star ends up in a single pixel.
+ */
+pub fn render_to_buffer(
+    camera: &Camera,
+    appearances: &[StarAppearance],
+    psf_sigma: f64,
+) -> Vec<f32> {
+    let (width, height) = camera.get_resolution();
+    let mut buffer = vec![0f32; width as usize * height as usize];
+    for (row, col, appearance) in camera.visible_stars(appearances) {
+        let lux = appearance.illuminance.to_lux();
+        if psf_sigma.is_nan() || psf_sigma <= 0. {
+            buffer[row as usize * width as usize + col as usize] += lux as f32;
+            continue;
+        }
+        let reach = (PSF_CUTOFF_IN_SIGMA * psf_sigma).ceil() as i64;
+        let normalisation = 1. / (2. * PI * psf_sigma.powi(2));
+        for dr in -reach..=reach {
+            for dc in -reach..=reach {
+                let r = row as i64 + dr;
+                let c = col as i64 + dc;
+                if r < 0 || c < 0 || r >= height as i64 || c >= width as i64 {
+                    continue;
+                }
+                let distance_squared = (dr * dr + dc * dc) as f64;
+                let weight = normalisation * (-distance_squared / (2. * psf_sigma.powi(2))).exp();
+                buffer[r as usize * width as usize + c as usize] += (lux * weight) as f32;
+            }
+        }
+    }
+    buffer
+}
+
+/*
+ * Sums up the illuminance of all stars that fall onto the same (row, column) pixel.
+ * Pixels without any star are not contained.
+ */
+pub fn accumulate_flux(
+    camera: &Camera,
+    appearances: &[StarAppearance],
+) -> HashMap<(u32, u32), Illuminance<f64>> {
+    let mut flux: HashMap<(u32, u32), Illuminance<f64>> = HashMap::new();
+    for (row, col, appearance) in camera.visible_stars(appearances) {
+        let pixel = flux.entry((row, col)).or_insert(IRRADIANCE_ZERO);
+        *pixel = *pixel + appearance.illuminance;
+    }
+    flux
+}
+
+/*
+ * Renders an 8-bit RGB image, stored row by row.
+ * The illuminance of every star is weighted with its colour and added to its pixel.
+ * The result is scaled linearly, such that the brightest channel of the image is 255.
+ */
+pub fn render_rgb(camera: &Camera, appearances: &[StarAppearance]) -> Vec<[u8; 3]> {
+    render_rgb_with_tone_map(camera, appearances, ToneMap::Linear)
+}
+
+/*
+ * Like render_rgb, but with the given mapping from linear flux to pixel values.
+ */
+pub fn render_rgb_with_tone_map(
+    camera: &Camera,
+    appearances: &[StarAppearance],
+    tone_map: ToneMap,
+) -> Vec<[u8; 3]> {
+    let (width, height) = camera.get_resolution();
+    let mut flux = vec![[0f64; 3]; width as usize * height as usize];
+    for (row, col, appearance) in camera.visible_stars(appearances) {
+        let lux = appearance.illuminance.to_lux();
+        let (r, g, b) = appearance.color.maximized_sRGB_tuple();
+        let pixel = &mut flux[row as usize * width as usize + col as usize];
+        for (channel, weight) in pixel.iter_mut().zip([r, g, b]) {
+            if weight.is_finite() {
+                *channel += lux * weight;
+            }
+        }
+    }
+    let max = flux.iter().flatten().fold(0., |a: f64, b| a.max(*b));
+    flux.iter()
+        .map(|pixel| pixel.map(|value| to_byte(tone_map.map(value, max))))
+        .collect()
+}
+
+fn to_byte(value: f64) -> u8 {
+    (value * 255.).round().clamp(0., 255.) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::srgb::sRGBColor,
+        units::{angle::ANGLE_ZERO, time::TIME_ZERO},
+    };
+    use astro_coords::{
+        cartesian::Cartesian, direction::Direction, ecliptic::Ecliptic, spherical::Spherical,
+    };
+    use simple_si_units::geometry::Angle;
+
+    fn test_camera(width: u32, height: u32) -> Camera {
+        Camera::new(
+            Cartesian::ORIGIN,
+            Direction::X,
+            Direction::Z,
+            Angle::from_degrees(30.),
+            width,
+            height,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn stars_in_the_same_pixel_sum_their_illuminance() {
+        let camera = test_camera(64, 48);
+        let star = |lux: f64, pos: Ecliptic| {
+            StarAppearance::new(
+                "Schnuffelpuff".to_string(),
+                Illuminance::from_lux(lux),
+                sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+                pos,
+                TIME_ZERO,
+            )
+        };
+        let stars = vec![
+            star(1.0, Ecliptic::X_DIRECTION),
+            star(2.5, Ecliptic::X_DIRECTION),
+            star(7.0, Ecliptic::Y_DIRECTION),
+        ];
+
+        let flux = accumulate_flux(&camera, &stars);
+
+        assert_eq!(flux.len(), 1);
+        let (row, col, _) = camera.visible_stars(&stars)[0];
+        assert!((flux[&(row, col)].to_lux() - 3.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn blue_star_produces_a_blue_pixel() {
+        let camera = test_camera(64, 48);
+        let stars = vec![StarAppearance::new(
+            "Schnuffelpuff".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(0.2, 0.4, 1.0),
+            Ecliptic::X_DIRECTION,
+            TIME_ZERO,
+        )];
+
+        let image = render_rgb(&camera, &stars);
+
+        let (row, col, _) = camera.visible_stars(&stars)[0];
+        let [r, g, b] = image[row as usize * 64 + col as usize];
+        assert_eq!(b, 255);
+        assert!(b > g && g > r);
+        assert_eq!(image.iter().filter(|pixel| **pixel != [0, 0, 0]).count(), 1);
+    }
+
+    #[test]
+    fn logarithmic_tone_map_keeps_faint_stars_visible() {
+        let camera = test_camera(64, 48);
+        let star = |lux: f64, longitude: f64| {
+            StarAppearance::new(
+                "Schnuffelpuff".to_string(),
+                Illuminance::from_lux(lux),
+                sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+                Spherical::new(Angle::from_degrees(longitude), ANGLE_ZERO).to_ecliptic(),
+                TIME_ZERO,
+            )
+        };
+        let stars = vec![star(1000., 0.), star(1., 5.)];
+        let visible = camera.visible_stars(&stars);
+        assert_eq!(visible.len(), 2);
+        let index = |i: usize| visible[i].0 as usize * 64 + visible[i].1 as usize;
+
+        let linear = render_rgb_with_tone_map(&camera, &stars, ToneMap::Linear);
+        let logarithmic = render_rgb_with_tone_map(&camera, &stars, ToneMap::Logarithmic);
+
+        assert_eq!(linear[index(0)], [255, 255, 255]);
+        assert_eq!(logarithmic[index(0)], [255, 255, 255]);
+        assert_eq!(linear[index(1)], [0, 0, 0]);
+        assert!(logarithmic[index(1)][0] > 0);
+    }
+
+    #[test]
+    fn single_star_peaks_at_its_pixel_and_decays_with_sigma() {
+        let width = 64;
+        let camera = test_camera(width, 48);
+        let star = StarAppearance::new(
+            "Schnuffelpuff".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+            Ecliptic::X_DIRECTION,
+            TIME_ZERO,
+        );
+        let sigma = 2.;
+
+        let buffer = render_to_buffer(&camera, &[star.clone()], sigma);
+
+        let (row, col, _) = camera.visible_stars(&[star])[0];
+        let pixel = |r: u32, c: u32| buffer[r as usize * width as usize + c as usize] as f64;
+        let peak = pixel(row, col);
+        let max = buffer.iter().fold(0f32, |a, b| a.max(*b)) as f64;
+        assert!((peak - max).abs() < 1e-9);
+        for distance in 1..4 {
+            let expected = (-(distance as f64).powi(2) / (2. * sigma * sigma)).exp();
+            let actual = pixel(row, col + distance) / peak;
+            assert!((actual - expected).abs() < 1e-5);
+        }
+        let total: f64 = buffer.iter().map(|value| *value as f64).sum();
+        assert!((total - 1.).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,59 @@
+/*
+ * Maps linear pixel values onto the displayable range from 0 to 1.
+ * In all cases, the brightest value of the image is mapped to 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ToneMap {
+    #[default]
+    Linear,
+    Logarithmic,
+    // https://www.cs.utah.edu/docs/techreports/2002/pdf/UUCS-02-001.pdf
+    Reinhard,
+}
+
+/*
+ * The ratio between the brightest value and a value that is still mapped to a visible level by the logarithmic tone map.
+ */
+const LOGARITHMIC_DYNAMIC_RANGE: f64 = 1e4;
+/*
+ * Scales the values before applying the Reinhard operator L / (1 + L).
+ */
+const REINHARD_EXPOSURE: f64 = 100.;
+
+impl ToneMap {
+    pub fn map(&self, value: f64, max: f64) -> f64 {
+        if max <= 0. || value.is_nan() {
+            return 0.;
+        }
+        let x = (value / max).clamp(0., 1.);
+        match self {
+            ToneMap::Linear => x,
+            ToneMap::Logarithmic => {
+                (1. + LOGARITHMIC_DYNAMIC_RANGE * x).log10()
+                    / (1. + LOGARITHMIC_DYNAMIC_RANGE).log10()
+            }
+            ToneMap::Reinhard => x * (1. + REINHARD_EXPOSURE) / (1. + REINHARD_EXPOSURE * x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::eq;
+
+    #[test]
+    fn all_tone_maps_keep_black_and_white() {
+        for tone_map in [ToneMap::Linear, ToneMap::Logarithmic, ToneMap::Reinhard] {
+            assert!(eq(tone_map.map(0., 10.), 0.));
+            assert!(eq(tone_map.map(10., 10.), 1.));
+        }
+    }
+
+    #[test]
+    fn compressing_tone_maps_lift_dark_values() {
+        let linear = ToneMap::Linear.map(1., 100.);
+        assert!(ToneMap::Logarithmic.map(1., 100.) > linear);
+        assert!(ToneMap::Reinhard.map(1., 100.) > linear);
+    }
+}
@@ -1,8 +1,19 @@
-use astro_coords::ecliptic::Ecliptic;
+use astro_coords::{direction::Direction, ecliptic::Ecliptic};
 use serde::{Deserialize, Serialize};
-use simple_si_units::{base::Time, electromagnetic::Illuminance, geometry::Angle};
+use simple_si_units::{
+    base::{Distance, Time},
+    electromagnetic::Illuminance,
+    geometry::Angle,
+};
 
-use crate::{astro_display::AstroDisplay, color::srgb::sRGBColor};
+use std::cmp::Ordering;
+
+use crate::{
+    astro_display::AstroDisplay,
+    color::srgb::sRGBColor,
+    coordinates::healpix::{number_of_sky_pixels, sky_pixel},
+    units::illuminance::illuminance_to_apparent_magnitude,
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StarAppearance {
@@ -56,18 +67,111 @@ impl StarAppearance {
 
     pub fn apparently_the_same(&self, other: &Self) -> bool {
         let angle_accuracy = Angle::from_degrees(0.03); //Rather high due to accos inaccuracy
+        self.apparently_the_same_within(other, angle_accuracy, 10.0)
+    }
 
+    /*
+     * The illuminances may differ by at most a factor of max_illuminance_ratio in either direction.
+     */
+    pub fn apparently_the_same_within(
+        &self,
+        other: &Self,
+        angle_accuracy: Angle<f64>,
+        max_illuminance_ratio: f64,
+    ) -> bool {
         if !self.pos.eq_within(&other.pos, angle_accuracy) {
             return false;
         }
         let illuminance_ratio = self.illuminance.to_lux() / other.illuminance.to_lux();
-        if !(0.1..=10.0).contains(&illuminance_ratio) {
+        if !(1. / max_illuminance_ratio..=max_illuminance_ratio).contains(&illuminance_ratio) {
             return false;
         }
         true
     }
 }
 
+/*
+ * Counts the stars in each of the equal-area sky pixels defined in coordinates::healpix.
+ */
+pub fn density_map(appearances: &[StarAppearance], nside: u32) -> Vec<u32> {
+    let mut counts = vec![0; number_of_sky_pixels(nside) as usize];
+    for appearance in appearances {
+        let dir = appearance.pos.spherical.to_direction();
+        counts[sky_pixel(&dir, nside) as usize] += 1;
+    }
+    counts
+}
+
+/*
+ * Returns the lower edge of each apparent magnitude bin together with the number of stars in it.
+ * The bins are contiguous, ranging from the brightest to the faintest star.
+ */
+pub fn magnitude_histogram(appearances: &[StarAppearance], bin_width: f64) -> Vec<(f64, usize)> {
+    if bin_width <= 0. {
+        return vec![];
+    }
+    let bin_indices: Vec<i64> = appearances
+        .iter()
+        .map(|appearance| illuminance_to_apparent_magnitude(&appearance.illuminance))
+        .filter(|magnitude| magnitude.is_finite())
+        .map(|magnitude| (magnitude / bin_width).floor() as i64)
+        .collect();
+    let (min_index, max_index) = match (bin_indices.iter().min(), bin_indices.iter().max()) {
+        (Some(min_index), Some(max_index)) => (*min_index, *max_index),
+        _ => return vec![],
+    };
+    let mut counts = vec![0; (max_index - min_index + 1) as usize];
+    for index in bin_indices {
+        counts[(index - min_index) as usize] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| ((min_index + i as i64) as f64 * bin_width, count))
+        .collect()
+}
+
+/*
+ * Returns the n stars with the highest illuminance, brightest first.
+ * Only the selected stars are sorted, so this is cheap for small n even on large catalogs.
+ */
+pub fn brightest_n(appearances: &[StarAppearance], n: usize) -> Vec<&StarAppearance> {
+    let mut refs: Vec<&StarAppearance> = appearances.iter().collect();
+    let by_descending_illuminance = |a: &&StarAppearance, b: &&StarAppearance| {
+        b.illuminance
+            .lux
+            .partial_cmp(&a.illuminance.lux)
+            .unwrap_or(Ordering::Equal)
+    };
+    if n < refs.len() {
+        if n == 0 {
+            return vec![];
+        }
+        refs.select_nth_unstable_by(n - 1, by_descending_illuminance);
+        refs.truncate(n);
+    }
+    refs.sort_by(by_descending_illuminance);
+    refs
+}
+
+/*
+ * Rotates the positions of all appearances by the given angle around the given axis.
+ */
+pub fn rotate_scene(appearances: &mut [StarAppearance], axis: &Direction, angle: Angle<f64>) {
+    for appearance in appearances.iter_mut() {
+        let rotated = appearance
+            .pos
+            .spherical
+            .to_direction()
+            .to_cartesian(Distance::from_m(1.))
+            .rotated(angle, axis)
+            .to_ecliptic();
+        if let Ok(pos) = rotated {
+            appearance.pos = pos;
+        }
+    }
+}
+
 impl AstroDisplay for StarAppearance {
     fn astro_display(&self) -> String {
         format!(
@@ -82,10 +186,156 @@ impl AstroDisplay for StarAppearance {
 
 #[cfg(test)]
 mod tests {
-    use crate::units::time::TIME_ZERO;
+    use simple_si_units::base::Distance;
+
+    use crate::{
+        real_data::stars::all::get_many_stars,
+        stars::random::random_stars::generate_random_stars,
+        tests::eq,
+        units::{illuminance::apparent_magnitude_to_illuminance, time::TIME_ZERO},
+    };
 
     use super::*;
 
+    #[test]
+    fn density_map_counts_every_star_once() {
+        let stars = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_appearance())
+            .collect::<Vec<StarAppearance>>();
+        let map = density_map(&stars, 4);
+        assert_eq!(map.len(), 192);
+        assert_eq!(map.iter().sum::<u32>() as usize, stars.len());
+    }
+
+    #[test]
+    fn stars_in_the_same_direction_share_a_pixel() {
+        let star = StarAppearance::new(
+            "Schnuffelpuff".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+            Ecliptic::X_DIRECTION,
+            TIME_ZERO,
+        );
+        let stars = vec![star.clone(), star];
+        let map = density_map(&stars, 8);
+        assert_eq!(map.iter().max(), Some(&2));
+    }
+
+    #[test]
+    fn magnitude_histogram_bins_stars_by_magnitude() {
+        let stars = [0.5, 1.2, 1.7, 3.1]
+            .iter()
+            .map(|magnitude| {
+                StarAppearance::new(
+                    "Schnuffelpuff".to_string(),
+                    apparent_magnitude_to_illuminance(*magnitude),
+                    sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+                    Ecliptic::X_DIRECTION,
+                    TIME_ZERO,
+                )
+            })
+            .collect::<Vec<StarAppearance>>();
+        let histogram = magnitude_histogram(&stars, 1.);
+        let counts = histogram
+            .iter()
+            .map(|(_, count)| *count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![1, 2, 0, 1]);
+        assert!(eq(histogram[0].0, 0.));
+        assert!(eq(histogram[3].0, 3.));
+    }
+
+    #[test]
+    fn faint_stars_outnumber_bright_stars() {
+        let max_distance = Distance::from_lyr(1000.);
+        let stars = generate_random_stars(max_distance)
+            .unwrap()
+            .iter()
+            .map(|star| star.to_star_appearance(TIME_ZERO))
+            .collect::<Vec<StarAppearance>>();
+        let histogram = magnitude_histogram(&stars, 1.);
+        let count_in_bin = |lower_edge: f64| {
+            histogram
+                .iter()
+                .find(|(edge, _)| (edge - lower_edge).abs() < 0.5)
+                .map(|(_, count)| *count)
+                .unwrap_or(0)
+        };
+        assert!(count_in_bin(5.) > count_in_bin(2.));
+    }
+
+    #[test]
+    fn brightest_stars_are_sorted_by_descending_illuminance() {
+        let stars = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_appearance())
+            .collect::<Vec<StarAppearance>>();
+        let brightest = brightest_n(&stars, 10);
+        assert_eq!(brightest.len(), 10);
+        for pair in brightest.windows(2) {
+            assert!(pair[0].illuminance >= pair[1].illuminance);
+        }
+        let dimmest_selected = brightest[9].illuminance;
+        let brighter_count = stars
+            .iter()
+            .filter(|star| star.illuminance > dimmest_selected)
+            .count();
+        assert!(brighter_count < 10);
+    }
+
+    #[test]
+    fn asking_for_more_stars_than_available_returns_all() {
+        let stars = [3.1, 0.5, 1.2]
+            .iter()
+            .map(|magnitude| {
+                StarAppearance::new(
+                    "Schnuffelpuff".to_string(),
+                    apparent_magnitude_to_illuminance(*magnitude),
+                    sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+                    Ecliptic::X_DIRECTION,
+                    TIME_ZERO,
+                )
+            })
+            .collect::<Vec<StarAppearance>>();
+        let brightest = brightest_n(&stars, 10);
+        assert_eq!(brightest.len(), 3);
+        assert_eq!(brightest[0], &stars[1]);
+        assert_eq!(brightest[1], &stars[2]);
+        assert_eq!(brightest[2], &stars[0]);
+    }
+
+    #[test]
+    fn rotating_by_a_full_circle_changes_nothing() {
+        let original = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_appearance())
+            .collect::<Vec<StarAppearance>>();
+        let mut rotated = original.clone();
+        let axis = Direction::new(1., 2., 3.).unwrap();
+        rotate_scene(&mut rotated, &axis, Angle::from_degrees(360.));
+        for (original, rotated) in original.iter().zip(rotated.iter()) {
+            assert!(original
+                .pos
+                .eq_within(&rotated.pos, Angle::from_degrees(1e-3)));
+        }
+    }
+
+    #[test]
+    fn rotating_a_quarter_circle_around_z_maps_x_to_y() {
+        let mut stars = vec![StarAppearance::new(
+            "Schnuffelpuff".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+            Ecliptic::X_DIRECTION,
+            TIME_ZERO,
+        )];
+        rotate_scene(&mut stars, &Direction::Z, Angle::from_degrees(90.));
+        assert!(stars[0]
+            .pos
+            .eq_within(&Ecliptic::Y_DIRECTION, Angle::from_degrees(1e-3)));
+    }
+
     #[test]
     fn star_is_apparently_the_same_with_itself() {
         let star = StarAppearance::new(
@@ -128,4 +378,20 @@ mod tests {
 
         assert!(!star.apparently_the_same(&other));
     }
+
+    #[test]
+    fn tighter_illuminance_ratio_rejects_what_default_accepts() {
+        let star = StarAppearance::new(
+            "Schnuffelpuff".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+            Ecliptic::X_DIRECTION,
+            TIME_ZERO,
+        );
+        let mut other = star.clone();
+        other.illuminance = Illuminance::from_lux(5.0);
+
+        assert!(star.apparently_the_same(&other));
+        assert!(!star.apparently_the_same_within(&other, Angle::from_degrees(0.03), 2.0));
+    }
 }
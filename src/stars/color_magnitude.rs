@@ -0,0 +1,34 @@
+use super::data::StarData;
+use crate::{
+    color::color_index::temperature_to_b_minus_v,
+    units::{luminous_intensity::luminous_intensity_to_absolute_magnitude, time::TIME_ZERO},
+};
+
+/*
+ * Returns (B-V, absolute magnitude) pairs at epoch, as plotted in a color-magnitude diagram.
+ */
+pub fn color_magnitude_points(stars: &[StarData]) -> Vec<(f64, f64)> {
+    stars
+        .iter()
+        .map(|star| {
+            let b_minus_v = temperature_to_b_minus_v(star.get_temperature(TIME_ZERO));
+            let absolute_magnitude =
+                luminous_intensity_to_absolute_magnitude(star.get_luminous_intensity(TIME_ZERO));
+            (b_minus_v, absolute_magnitude)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{real_data::stars::SUN, tests::eq_within};
+
+    #[test]
+    fn sun_in_color_magnitude_diagram() {
+        let points = color_magnitude_points(&[SUN.to_star_data()]);
+        let (b_minus_v, absolute_magnitude) = points[0];
+        assert!(eq_within(b_minus_v, 0.65, 0.05));
+        assert!(eq_within(absolute_magnitude, 4.83, 0.05));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::stars::appearance::StarAppearance;
+use crate::{stars::appearance::StarAppearance, units::angle::ANGLE_ZERO};
 use serde::{Deserialize, Serialize};
 use simple_si_units::geometry::Angle;
 use std::cmp::Ordering;
@@ -11,8 +11,8 @@ pub struct Connection {
 }
 
 impl Connection {
-    fn new(from: usize, to: usize, stars: &[StarAppearance]) -> Self {
-        let distance = stars[from].get_pos().angle_to(stars[to].get_pos());
+    fn new(from: usize, to: usize, distances: &[Vec<Angle<f64>>]) -> Self {
+        let distance = distances[from][to];
         Connection { from, to, distance }
     }
 
@@ -64,11 +64,26 @@ fn is_reachable_within(
     false
 }
 
-fn sorted_connections(stars: &[StarAppearance]) -> Vec<Connection> {
-    let mut connections: Vec<Connection> = Vec::new();
+/*
+ * The angular distance between every pair of stars, computed once so that the connection algorithm does not need to recompute it over and over.
+ */
+pub(super) fn angular_distances(stars: &[StarAppearance]) -> Vec<Vec<Angle<f64>>> {
+    let mut distances = vec![vec![ANGLE_ZERO; stars.len()]; stars.len()];
     for i in 0..stars.len() {
         for j in i + 1..stars.len() {
-            connections.push(Connection::new(i, j, stars));
+            let distance = stars[i].get_pos().angle_to(stars[j].get_pos());
+            distances[i][j] = distance;
+            distances[j][i] = distance;
+        }
+    }
+    distances
+}
+
+fn sorted_connections(distances: &[Vec<Angle<f64>>]) -> Vec<Connection> {
+    let mut connections: Vec<Connection> = Vec::new();
+    for i in 0..distances.len() {
+        for j in i + 1..distances.len() {
+            connections.push(Connection::new(i, j, distances));
         }
     }
     connections.sort_by(|a, b| {
@@ -79,27 +94,25 @@ fn sorted_connections(stars: &[StarAppearance]) -> Vec<Connection> {
     connections
 }
 
-fn nearest_neighbours(i: usize, stars: &[StarAppearance]) -> Vec<usize> {
+fn nearest_neighbours(i: usize, distances: &[Vec<Angle<f64>>]) -> Vec<usize> {
     let mut neighbours: Vec<usize> = Vec::new();
-    for j in 0..stars.len() {
+    for j in 0..distances.len() {
         if i != j {
             neighbours.push(j);
         }
     }
     neighbours.sort_by(|a, b| {
-        stars[i]
-            .get_pos()
-            .angle_to(stars[*a].get_pos())
-            .partial_cmp(&stars[i].get_pos().angle_to(stars[*b].get_pos()))
+        distances[i][*a]
+            .partial_cmp(&distances[i][*b])
             .unwrap_or(Ordering::Equal)
     });
     neighbours
 }
 
-fn all_nearest_neighbours(stars: &[StarAppearance]) -> Vec<Vec<usize>> {
+fn all_nearest_neighbours(distances: &[Vec<Angle<f64>>]) -> Vec<Vec<usize>> {
     let mut all_neighbours: Vec<Vec<usize>> = Vec::new();
-    for i in 0..stars.len() {
-        all_neighbours.push(nearest_neighbours(i, stars));
+    for i in 0..distances.len() {
+        all_neighbours.push(nearest_neighbours(i, distances));
     }
     all_neighbours
 }
@@ -119,8 +132,13 @@ fn get_max_allowed_steps(i: usize, j: usize, all_nearest_neighbours: &[Vec<usize
 }
 
 pub(super) fn collect_connections(stars: &[StarAppearance]) -> Vec<Connection> {
-    let all_nearest_neighbours = all_nearest_neighbours(stars);
-    let all_connections = sorted_connections(stars);
+    let distances = angular_distances(stars);
+    collect_connections_with_distances(&distances)
+}
+
+pub(super) fn collect_connections_with_distances(distances: &[Vec<Angle<f64>>]) -> Vec<Connection> {
+    let all_nearest_neighbours = all_nearest_neighbours(distances);
+    let all_connections = sorted_connections(distances);
     let mut connections: Vec<Connection> = Vec::new();
     for connection in all_connections {
         if connections.contains(&connection) {
@@ -138,19 +156,64 @@ pub(super) fn collect_connections(stars: &[StarAppearance]) -> Vec<Connection> {
     connections
 }
 
-#[cfg(test)]
+/*
+ * How the cost of connecting two stars is computed when building a minimum spanning tree.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionWeighting {
+    AngularDistance,
+    // The angular distance is multiplied with the mean of both stars' inverse illuminance, relative to the brightest star.
+    InverseBrightness,
+}
+
+fn connection_costs(
+    stars: &[StarAppearance],
+    distances: &[Vec<Angle<f64>>],
+    weighting: ConnectionWeighting,
+) -> Vec<Vec<Angle<f64>>> {
+    match weighting {
+        ConnectionWeighting::AngularDistance => distances.to_vec(),
+        ConnectionWeighting::InverseBrightness => {
+            let brightest = stars
+                .iter()
+                .fold(0., |max: f64, star| max.max(star.illuminance.lux));
+            let relative_faintness = stars
+                .iter()
+                .map(|star| {
+                    if star.illuminance.lux > 0. {
+                        brightest / star.illuminance.lux
+                    } else {
+                        f64::INFINITY
+                    }
+                })
+                .collect::<Vec<f64>>();
+            distances
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(j, distance)| {
+                            *distance * ((relative_faintness[i] + relative_faintness[j]) / 2.)
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+    }
+}
+
 fn find_nearest_neighbour(
     index: usize,
-    stars: &[StarAppearance],
-    excluding: &Vec<usize>,
+    distances: &[Vec<Angle<f64>>],
+    excluding: &[usize],
 ) -> Option<usize> {
     let mut nearest_neighbour = None;
-    let pos = stars[index].get_pos();
-    for j in 0..stars.len() {
+    for j in 0..distances.len() {
         if index != j && !excluding.contains(&j) {
-            let distance = stars[j].get_pos().angle_to(pos);
+            let distance = distances[index][j];
             if let Some(nn) = nearest_neighbour {
-                let nn_distance = stars[nn as usize].get_pos().angle_to(pos);
+                let nn_distance = distances[index][nn];
                 if distance < nn_distance {
                     nearest_neighbour = Some(j);
                 }
@@ -164,31 +227,45 @@ fn find_nearest_neighbour(
 
 #[cfg(test)]
 fn minimum_spanning_tree(stars: &[StarAppearance]) -> Vec<Connection> {
-    use crate::units::angle::FULL_CIRC;
+    minimum_spanning_tree_with_weighting(stars, ConnectionWeighting::AngularDistance)
+}
 
+/*
+ * Connects all stars with the cheapest set of connections, where the cost of a connection depends on the weighting.
+ */
+pub fn minimum_spanning_tree_with_weighting(
+    stars: &[StarAppearance],
+    weighting: ConnectionWeighting,
+) -> Vec<Connection> {
     // This is Prim's algorithm
     let mut connections = Vec::new();
     if stars.len() < 2 {
         return connections;
     }
+    let distances = angular_distances(stars);
+    let costs = connection_costs(stars, &distances, weighting);
     let mut visited = vec![0];
     while visited.len() < stars.len() {
-        let mut current_best = Connection {
-            to: 0,
-            from: 0,
-            distance: FULL_CIRC,
-        };
+        let mut current_best: Option<(usize, usize)> = None;
         for i in &visited {
-            let nn = find_nearest_neighbour(*i, stars, &visited);
+            let nn = find_nearest_neighbour(*i, &costs, &visited);
             if let Some(nn) = nn {
-                let connection = Connection::new(*i, nn, stars);
-                if connection.distance < current_best.distance {
-                    current_best = connection;
+                let is_better = match current_best {
+                    Some((from, to)) => costs[*i][nn] < costs[from][to],
+                    None => true,
+                };
+                if is_better {
+                    current_best = Some((*i, nn));
                 }
             }
         }
-        visited.push(current_best.to);
-        connections.push(current_best);
+        match current_best {
+            Some((from, to)) => {
+                visited.push(to);
+                connections.push(Connection::new(from, to, &distances));
+            }
+            None => break,
+        }
     }
     connections
 }
@@ -202,7 +279,7 @@ mod tests {
         color::srgb::sRGBColor,
         real_data::stars::all::get_many_stars,
         stars::constellation::collect_constellations,
-        units::{angle::ANGLE_ZERO, tests::ANGLE_TEST_ACCURACY, time::TIME_ZERO},
+        units::{angle::angle_eq_within, tests::ANGLE_TEST_ACCURACY, time::TIME_ZERO},
     };
 
     use super::*;
@@ -241,13 +318,14 @@ mod tests {
     fn nearest_neighbours_of_line_are_sorted() {
         let size = 10;
         let stars = stars_in_line(size);
-        let neighbours = nearest_neighbours(0, &stars);
+        let distances = angular_distances(&stars);
+        let neighbours = nearest_neighbours(0, &distances);
         assert!(neighbours.len() == size - 1);
         for i in 1..size {
             assert!(neighbours[i - 1] == i);
         }
 
-        let nearest_neighbours = nearest_neighbours(size - 1, &stars);
+        let nearest_neighbours = nearest_neighbours(size - 1, &distances);
         assert!(nearest_neighbours.len() == size - 1);
         for i in 1..size {
             assert!(nearest_neighbours[i - 1] == size - 1 - i);
@@ -257,11 +335,103 @@ mod tests {
     #[test]
     fn all_nearest_neighbours_for_short_line() {
         let stars = stars_in_line(3);
-        let all_neighbours = all_nearest_neighbours(&stars);
+        let all_neighbours = all_nearest_neighbours(&angular_distances(&stars));
         let expected = vec![vec![1, 2], vec![0, 2], vec![1, 0]];
         assert!(all_neighbours == expected);
     }
 
+    #[test]
+    fn cached_angular_distances_match_direct_computation() {
+        let stars = stars_in_line(10);
+        let distances = angular_distances(&stars);
+        for i in 0..stars.len() {
+            for j in 0..stars.len() {
+                let expected = stars[i].get_pos().angle_to(stars[j].get_pos());
+                assert!(angle_eq_within(
+                    distances[i][j],
+                    expected,
+                    ANGLE_TEST_ACCURACY
+                ));
+                assert!(angle_eq_within(
+                    distances[i][j],
+                    distances[j][i],
+                    ANGLE_TEST_ACCURACY
+                ));
+            }
+        }
+    }
+
+    /*
+     * The connection algorithm as it was before the distances were cached, computing every angle from the star positions.
+     */
+    fn uncached_connections(stars: &[StarAppearance]) -> Vec<Connection> {
+        let distance = |i: usize, j: usize| stars[i].get_pos().angle_to(stars[j].get_pos());
+        let all_nearest_neighbours: Vec<Vec<usize>> = (0..stars.len())
+            .map(|i| {
+                let mut neighbours: Vec<usize> = (0..stars.len()).filter(|&j| j != i).collect();
+                neighbours.sort_by(|a, b| {
+                    distance(i, *a)
+                        .partial_cmp(&distance(i, *b))
+                        .unwrap_or(Ordering::Equal)
+                });
+                neighbours
+            })
+            .collect();
+        let mut all_connections = Vec::new();
+        for i in 0..stars.len() {
+            for j in i + 1..stars.len() {
+                all_connections.push(Connection {
+                    from: i,
+                    to: j,
+                    distance: distance(i, j),
+                });
+            }
+        }
+        all_connections.sort_by(|a, b| {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap_or(Ordering::Equal)
+        });
+        let mut connections: Vec<Connection> = Vec::new();
+        for connection in all_connections {
+            if connections.contains(&connection) {
+                continue;
+            }
+            let (start, end) = connection.get_indices();
+            let max_steps = get_max_allowed_steps(start, end, &all_nearest_neighbours);
+            if !is_reachable_within(start, end, max_steps, &connections) {
+                connections.push(connection);
+            }
+        }
+        connections
+    }
+
+    #[test]
+    fn connections_with_cached_distances_match_connections_from_stars() {
+        let all_stars = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_data())
+            .collect::<Vec<_>>();
+        let all_consteallations = collect_constellations(&all_stars[..], TIME_ZERO);
+        for constellation in all_consteallations {
+            let stars = constellation.get_stars();
+            let distances = angular_distances(stars);
+            let cached = collect_connections_with_distances(&distances);
+            let uncached = uncached_connections(stars);
+            assert_eq!(cached, uncached);
+            for connection in cached {
+                let expected = stars[connection.from]
+                    .get_pos()
+                    .angle_to(stars[connection.to].get_pos());
+                assert!(angle_eq_within(
+                    connection.distance,
+                    expected,
+                    ANGLE_TEST_ACCURACY
+                ));
+            }
+        }
+    }
+
     #[test]
     fn is_reachable() {
         let size = 10;
@@ -284,6 +454,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn brightness_weighted_tree_prefers_bright_stars() {
+        let star = |name: &str, longitude: f64, latitude: f64, lux: f64| {
+            StarAppearance::new(
+                name.to_string(),
+                Illuminance::from_lux(lux),
+                sRGBColor::WHITE,
+                Spherical::new(
+                    Angle::from_degrees(longitude),
+                    Angle::from_degrees(latitude),
+                )
+                .to_ecliptic(),
+                TIME_ZERO,
+            )
+        };
+        let stars = vec![
+            star("Start", 0., 0., 1.),
+            star("End", 20., 0., 1.),
+            star("Bright", 10., 1., 10.),
+            star("Faint", 10., -1., 0.1),
+        ];
+        let connects = |connections: &[Connection], i: usize, j: usize| {
+            connections.contains(&Connection {
+                from: i,
+                to: j,
+                distance: ANGLE_ZERO,
+            })
+        };
+
+        let connections =
+            minimum_spanning_tree_with_weighting(&stars, ConnectionWeighting::InverseBrightness);
+
+        assert_eq!(connections.len(), 3);
+        assert!(connects(&connections, 0, 2));
+        assert!(connects(&connections, 1, 2));
+        assert!(!connects(&connections, 0, 3));
+        assert!(!connects(&connections, 1, 3));
+    }
+
     #[test]
     fn minimum_spanning_tree_has_length_n_minus_1() {
         let all_stars = get_many_stars()
@@ -0,0 +1,109 @@
+use super::svg::mean_direction;
+use crate::coordinates::projection::gnomonic_projection;
+use astro_coords::direction::Direction;
+use std::cmp::Ordering;
+
+/*
+ * The corners of the smallest spherical polygon that contains all directions, ordered counterclockwise as seen from outside the sphere.
+ * The directions are projected gnomonically around their mean direction. This maps great circles onto straight lines, so the planar convex hull of the projected points is the spherical one:
+ * https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
+ * If the directions do not fit into the hemisphere around their mean, there is no such polygon and an empty vector is returned.
+ */
+pub fn spherical_convex_hull(dirs: &[Direction]) -> Vec<Direction> {
+    if dirs.len() < 3 {
+        return dirs.to_vec();
+    }
+    let center = match mean_direction(dirs) {
+        Some(center) => center,
+        None => return vec![],
+    };
+    let mut points = Vec::with_capacity(dirs.len());
+    for (i, dir) in dirs.iter().enumerate() {
+        match gnomonic_projection(dir, &center) {
+            Some((x, y)) => points.push((x, y, i)),
+            None => return vec![],
+        }
+    }
+    points.sort_by(|a, b| {
+        (a.0, a.1)
+            .partial_cmp(&(b.0, b.1))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut hull: Vec<(f64, f64, usize)> = Vec::with_capacity(2 * points.len());
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let lower_len = hull.len();
+        for point in pass {
+            while hull.len() >= lower_len + 2
+                && turn(&hull[hull.len() - 2], &hull[hull.len() - 1], &point) <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+    }
+    hull.into_iter().map(|(_, _, i)| dirs[i].clone()).collect()
+}
+
+/*
+ * Positive if a, b, c make a counterclockwise turn.
+ */
+fn turn(a: &(f64, f64, usize), b: &(f64, f64, usize), c: &(f64, f64, usize)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::projection::{components, cross, dot},
+        real_data::stars::all::get_many_stars,
+        stars::constellation::collect_constellations,
+        units::time::TIME_ZERO,
+    };
+
+    #[test]
+    fn hull_of_orion_contains_all_its_stars() {
+        let all_stars = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_data())
+            .collect::<Vec<_>>();
+        let constellations = collect_constellations(&all_stars, TIME_ZERO);
+        let orion = constellations
+            .iter()
+            .find(|constellation| constellation.get_name() == "Orion")
+            .unwrap();
+        let dirs = orion
+            .get_stars()
+            .iter()
+            .map(|star| star.pos.spherical.to_direction())
+            .collect::<Vec<Direction>>();
+
+        let hull = spherical_convex_hull(&dirs);
+
+        assert!(hull.len() >= 3);
+        assert!(hull.len() < dirs.len());
+        for corner in hull.iter() {
+            assert!(dirs.iter().any(|dir| dir.eq_within(corner, 1e-12)));
+        }
+        for (i, corner) in hull.iter().enumerate() {
+            let next = &hull[(i + 1) % hull.len()];
+            let normal = cross(&components(corner), &components(next));
+            for dir in dirs.iter() {
+                assert!(dot(&normal, &components(dir)) > -1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn directions_around_the_whole_sphere_have_no_hull() {
+        let dirs = vec![
+            Direction::X,
+            Direction::Y,
+            Direction::Z,
+            Direction::new(-1., -1., -1.).unwrap(),
+        ];
+        assert!(spherical_convex_hull(&dirs).is_empty());
+    }
+}
@@ -5,6 +5,8 @@ use simple_si_units::base::Time;
 use self::connection::{collect_connections, Connection};
 
 pub mod connection;
+pub mod hull;
+pub mod svg;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Constellation {
@@ -0,0 +1,104 @@
+use super::Constellation;
+use crate::coordinates::projection::gnomonic_projection;
+use astro_coords::direction::Direction;
+use std::fmt::Write;
+
+const MARGIN: f64 = 0.9;
+const STAR_RADIUS: f64 = 3.;
+
+/*
+ * Draws the stars and connections of a constellation, projected around their mean direction.
+ * As on a star chart, north is up and east is to the left.
+ */
+pub fn constellation_to_svg(constellation: &Constellation, width: u32, height: u32) -> String {
+    let directions = constellation
+        .stars
+        .iter()
+        .map(|star| star.pos.spherical.to_direction())
+        .collect::<Vec<Direction>>();
+    let projected = match mean_direction(&directions) {
+        Some(center) => directions
+            .iter()
+            .map(|dir| gnomonic_projection(dir, &center))
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+
+    let max_extent = projected
+        .iter()
+        .flatten()
+        .map(|(x, y)| x.abs().max(y.abs()))
+        .fold(0., f64::max);
+    let scale = if max_extent > 0. {
+        MARGIN * width.min(height) as f64 / 2. / max_extent
+    } else {
+        1.
+    };
+    let to_pixel = |(x, y): (f64, f64)| {
+        (
+            width as f64 / 2. - x * scale,
+            height as f64 / 2. - y * scale,
+        )
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        width, height
+    );
+    for connection in constellation.connections.iter() {
+        let (from, to) = connection.get_indices();
+        if let (Some(Some(from)), Some(Some(to))) = (projected.get(from), projected.get(to)) {
+            let (x1, y1) = to_pixel(*from);
+            let (x2, y2) = to_pixel(*to);
+            let _ = writeln!(
+                svg,
+                "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"black\" />",
+                x1, y1, x2, y2
+            );
+        }
+    }
+    for point in projected.iter().flatten() {
+        let (x, y) = to_pixel(*point);
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{}\" fill=\"black\" />",
+            x, y, STAR_RADIUS
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub(super) fn mean_direction(directions: &[Direction]) -> Option<Direction> {
+    let (x, y, z) = directions.iter().fold((0., 0., 0.), |(x, y, z), dir| {
+        (x + dir.x(), y + dir.y(), z + dir.z())
+    });
+    Direction::new(x, y, z).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        real_data::stars::all::get_many_stars, stars::constellation::collect_constellations,
+        units::time::TIME_ZERO,
+    };
+
+    #[test]
+    fn svg_contains_a_line_per_connection_and_a_circle_per_star() {
+        let all_stars = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_data())
+            .collect::<Vec<_>>();
+        let constellations = collect_constellations(&all_stars, TIME_ZERO);
+        let orion = constellations
+            .iter()
+            .find(|constellation| constellation.get_name() == "Orion")
+            .unwrap();
+
+        let svg = constellation_to_svg(orion, 800, 600);
+
+        assert_eq!(svg.matches("<line").count(), orion.get_connections().len());
+        assert_eq!(svg.matches("<circle").count(), orion.get_stars().len());
+    }
+}
@@ -1,11 +1,34 @@
 use super::{
     appearance::StarAppearance, evolution::StarDataEvolution, fate::StarFate,
-    physical_parameters::StarPhysicalParameters,
+    physical_parameters::StarPhysicalParameters, proper_motion::ProperMotion,
+    random::parsec::data::ParsecData,
 };
-use crate::{color::srgb::sRGBColor, units::luminous_intensity::luminous_intensity_to_illuminance};
-use astro_coords::{cartesian::Cartesian, ecliptic::Ecliptic};
+use crate::{
+    astro_display::AstroDisplay,
+    color::srgb::sRGBColor,
+    coordinates::earth_equatorial::right_ascension_and_declination,
+    error::AstroUtilError,
+    units::{
+        angle::ANGLE_ZERO,
+        distance::{light_travel_time, SOLAR_RADIUS},
+        illuminance::illuminance_to_apparent_magnitude,
+        luminous_intensity::{
+            luminous_intensity_to_absolute_magnitude, luminous_intensity_to_illuminance,
+        },
+        time::{BILLION_YEARS, TIME_ZERO},
+    },
+};
+use astro_coords::{cartesian::Cartesian, direction::Direction, ecliptic::Ecliptic};
 use serde::{Deserialize, Serialize};
 use simple_si_units::base::{Distance, Luminosity, Mass, Temperature, Time};
+use std::ops::{Add, Mul};
+
+/*
+ * A hashable identifier of a star, derived from its name and position at epoch.
+ * Stars with the same name and position share an id, so it is only unique within a catalogue without duplicates.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StarId(pub u64);
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StarData {
@@ -13,7 +36,10 @@ pub struct StarData {
     pub(super) constellation: Option<String>,
     pub(super) params: StarPhysicalParameters,
     pub(super) pos: Cartesian,
+    #[serde(default)]
     pub(super) evolution: StarDataEvolution,
+    #[serde(default)]
+    pub(super) proper_motion: Option<ProperMotion>,
 }
 
 impl StarData {
@@ -30,6 +56,7 @@ impl StarData {
             pos,
             constellation,
             evolution,
+            proper_motion: None,
         }
     }
 
@@ -66,6 +93,32 @@ impl StarData {
             .apply_to_luminous_intensity(self.params.luminous_intensity, time)
     }
 
+    /*
+     * None is returned if the star emits no light.
+     */
+    pub fn get_absolute_magnitude(&self) -> Option<f64> {
+        let luminous_intensity = self.params.luminous_intensity;
+        if luminous_intensity.cd.is_nan() || luminous_intensity.cd <= 0. {
+            return None;
+        }
+        Some(luminous_intensity_to_absolute_magnitude(luminous_intensity))
+    }
+
+    /*
+     * The apparent magnitude as seen from the origin at epoch.
+     * None is returned if the star emits no light or sits right at the origin.
+     */
+    pub fn get_apparent_magnitude(&self) -> Option<f64> {
+        self.get_absolute_magnitude()?;
+        let distance = self.get_distance_at_epoch();
+        if distance.m.is_nan() || distance.m <= 0. {
+            return None;
+        }
+        let illuminance =
+            luminous_intensity_to_illuminance(&self.params.luminous_intensity, &distance);
+        Some(illuminance_to_apparent_magnitude(&illuminance))
+    }
+
     pub const fn get_temperature_at_epoch(&self) -> Temperature<f64> {
         self.params.temperature
     }
@@ -95,8 +148,25 @@ impl StarData {
         &self.pos
     }
 
-    pub fn get_pos(&self, _time: Time<f64>) -> Cartesian {
-        self.pos.clone()
+    /*
+     * Stars without a proper motion stay where they are.
+     */
+    pub fn get_pos(&self, time: Time<f64>) -> Cartesian {
+        match &self.proper_motion {
+            Some(proper_motion) => proper_motion.apply(&self.pos, time),
+            None => self.pos.clone(),
+        }
+    }
+
+    pub fn position_at_time(
+        &self,
+        time_since_epoch: Time<f64>,
+    ) -> Result<Direction, AstroUtilError> {
+        Ok(self.get_pos(time_since_epoch).to_direction()?)
+    }
+
+    pub const fn get_proper_motion(&self) -> Option<ProperMotion> {
+        self.proper_motion
     }
 
     pub fn get_time_until_death(&self, time_since_epoch: Time<f64>) -> Option<Time<f64>> {
@@ -111,6 +181,23 @@ impl StarData {
         &self.evolution.fate
     }
 
+    pub fn id(&self) -> StarId {
+        // https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+        let coordinates = [self.pos.x.m, self.pos.y.m, self.pos.z.m];
+        // Adding zero maps -0 onto 0, so that both produce the same bytes.
+        let bytes = self.name.bytes().chain(
+            coordinates
+                .into_iter()
+                .flat_map(|coordinate| (coordinate + 0.).to_bits().to_le_bytes()),
+        );
+        let hash = bytes.fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        });
+        StarId(hash)
+    }
+
     pub fn set_name(&mut self, name: String) {
         self.name = name;
     }
@@ -151,6 +238,10 @@ impl StarData {
         self.pos = pos;
     }
 
+    pub fn set_proper_motion(&mut self, proper_motion: Option<ProperMotion>) {
+        self.proper_motion = proper_motion;
+    }
+
     pub fn get_evolution(&self) -> &StarDataEvolution {
         &self.evolution
     }
@@ -160,16 +251,50 @@ impl StarData {
     }
 
     pub fn to_star_appearance(&self, time_since_epoch: Time<f64>) -> StarAppearance {
+        self.to_star_appearance_from(&Cartesian::ORIGIN, time_since_epoch)
+    }
+
+    /*
+     * Like to_star_appearance, but instead of falling back to a default colour or direction, an error is returned if the temperature or the distance of the star is not known.
+     * Unknown values are those that are NaN, zero or negative.
+     */
+    pub fn to_star_appearance_strict(
+        &self,
+        time_since_epoch: Time<f64>,
+    ) -> Result<StarAppearance, AstroUtilError> {
+        let temperature = self.get_temperature(time_since_epoch);
+        if temperature.K.is_nan() || temperature.K <= 0. {
+            return Err(AstroUtilError::DataNotAvailable(format!(
+                "Temperature of {}",
+                self.name
+            )));
+        }
+        let distance = self.get_distance(time_since_epoch);
+        if distance.m.is_nan() || distance.m <= 0. {
+            return Err(AstroUtilError::DataNotAvailable(format!(
+                "Distance of {}",
+                self.name
+            )));
+        }
+        Ok(self.to_star_appearance(time_since_epoch))
+    }
+
+    /*
+     * The appearance of the star as seen from an observer that is not located at the origin.
+     */
+    pub fn to_star_appearance_from(
+        &self,
+        observer: &Cartesian,
+        time_since_epoch: Time<f64>,
+    ) -> StarAppearance {
+        let relative_position = &self.get_pos(time_since_epoch) - observer;
         let luminous_intensity = self.get_luminous_intensity(time_since_epoch);
-        let illuminance = luminous_intensity_to_illuminance(
-            &luminous_intensity,
-            &self.get_distance(time_since_epoch),
-        );
+        let illuminance =
+            luminous_intensity_to_illuminance(&luminous_intensity, &relative_position.length());
 
         let color = sRGBColor::from_temperature(self.get_temperature(time_since_epoch));
 
-        let pos = self
-            .get_pos(time_since_epoch)
+        let pos = relative_position
             .to_ecliptic()
             .unwrap_or(Ecliptic::X_DIRECTION);
 
@@ -182,6 +307,105 @@ impl StarData {
         }
     }
 
+    /*
+     * The colour of a star of the given initial mass and age, looked up directly in the PARSEC models.
+     * Without any data for the mass, black is returned.
+     */
+    pub fn color_at_age(mass: Mass<f64>, age: Time<f64>, parsec_data: &ParsecData) -> sRGBColor {
+        match parsec_data.get_temperature(mass, age) {
+            Some(temperature) => sRGBColor::from_temperature(temperature),
+            None => sRGBColor::from_sRGB(0., 0., 0.),
+        }
+    }
+
+    /*
+     * Blends the physical parameters, age and position linearly between self (t = 0) and other (t = 1).
+     * Optional values are only blended if both snapshots have them, otherwise those of self are kept.
+     * The colour of the resulting star follows from the blended temperature.
+     */
+    pub fn interpolate(&self, other: &StarData, t: f64) -> StarData {
+        let params = StarPhysicalParameters {
+            mass: lerp_option(self.params.mass, other.params.mass, t),
+            radius: lerp_option(self.params.radius, other.params.radius, t),
+            luminous_intensity: lerp(
+                self.params.luminous_intensity,
+                other.params.luminous_intensity,
+                t,
+            ),
+            temperature: lerp(self.params.temperature, other.params.temperature, t),
+        };
+        let mut evolution = self.evolution.clone();
+        evolution.age = lerp_option(self.evolution.age, other.evolution.age, t);
+        let pos = &(self.pos.clone() * (1. - t)) + &(other.pos.clone() * t);
+        StarData {
+            name: self.name.clone(),
+            constellation: self.constellation.clone(),
+            params,
+            pos,
+            evolution,
+            proper_motion: self.proper_motion,
+        }
+    }
+
+    /*
+     * Lists physically implausible values and combinations, e.g. for checking imported data.
+     */
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        const MAX_RADIUS: Distance<f64> = Distance {
+            m: 3_000. * SOLAR_RADIUS.m,
+        };
+        const AGE_OF_UNIVERSE: Time<f64> = Time {
+            s: 13.8 * BILLION_YEARS.s,
+        };
+
+        let mut problems = vec![];
+        let temperature = self.params.temperature;
+        if temperature.K.is_nan() || temperature.K <= 0. {
+            problems.push(format!("Temperature {} is not positive.", temperature));
+        }
+        if self.params.luminous_intensity.cd.is_nan() || self.params.luminous_intensity.cd < 0. {
+            problems.push(format!(
+                "Luminous intensity {} is negative.",
+                self.params.luminous_intensity
+            ));
+        }
+        if let Some(mass) = self.params.mass {
+            if mass.kg.is_nan() || mass.kg <= 0. {
+                problems.push(format!("Mass {} is not positive.", mass));
+            }
+        }
+        if let Some(radius) = self.params.radius {
+            if radius.m.is_nan() || radius.m <= 0. {
+                problems.push(format!("Radius {} is not positive.", radius));
+            } else if radius > MAX_RADIUS {
+                problems.push(format!(
+                    "Radius {} is larger than that of any known star.",
+                    radius
+                ));
+            }
+            if self.params.mass.is_none() {
+                problems.push("Radius is given, but mass is not.".to_string());
+            }
+        }
+        if let Some(age) = self.evolution.age {
+            if age.s < 0. {
+                problems.push(format!("Age {} is negative.", age));
+            } else if age > AGE_OF_UNIVERSE {
+                problems.push(format!("Age {} exceeds the age of the universe.", age));
+            }
+        }
+        let distance = self.pos.length();
+        if !distance.m.is_finite() {
+            problems.push(format!("Distance {} is not finite.", distance));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn similar_within_order_of_magnitude(&self, other: &Self) -> bool {
         use crate::units::luminous_intensity::luminous_intensity_to_absolute_magnitude;
@@ -251,10 +475,122 @@ impl StarData {
     }
 }
 
+/*
+ * Fields that are not known are shown as "unknown".
+ */
+impl AstroDisplay for StarData {
+    fn astro_display(&self) -> String {
+        let or_unknown = |value: Option<String>| value.unwrap_or_else(|| "unknown".to_string());
+        let direction = self.pos.to_ecliptic().ok().map(|dir| dir.to_string());
+        format!(
+            "Star: {}\nMass: {}\nRadius: {}\nLuminous intensity: {}\nTemperature: {}\nAge: {}\nDistance: {}\nDirection: {}",
+            self.name.astro_display(),
+            or_unknown(self.params.mass.map(|mass| mass.astro_display())),
+            or_unknown(self.params.radius.map(|radius| radius.astro_display())),
+            self.params.luminous_intensity.astro_display(),
+            self.params.temperature.astro_display(),
+            or_unknown(self.evolution.age.map(|age| age.astro_display())),
+            self.get_distance_at_epoch().astro_display(),
+            or_unknown(direction)
+        )
+    }
+}
+
+fn lerp<T>(a: T, b: T, t: f64) -> T
+where
+    T: Mul<f64, Output = T> + Add<Output = T>,
+{
+    a * (1. - t) + b * t
+}
+
+fn lerp_option<T>(a: Option<T>, b: Option<T>, t: f64) -> Option<T>
+where
+    T: Mul<f64, Output = T> + Add<Output = T>,
+{
+    match (a, b) {
+        (Some(a), Some(b)) => Some(lerp(a, b, t)),
+        (a, _) => a,
+    }
+}
+
+/*
+ * The distance in space between two stars at epoch, as opposed to their angular separation on the sky.
+ * None is returned if the position of either star is not known well enough to yield a finite distance.
+ */
+pub fn physical_separation(a: &StarData, b: &StarData) -> Option<Distance<f64>> {
+    let separation = (&a.pos - &b.pos).length();
+    if separation.m.is_finite() {
+        Some(separation)
+    } else {
+        None
+    }
+}
+
+/*
+ * The difference between the longest and the shortest light travel time from the stars to the origin.
+ * It is the range of epochs that the stars are seen at, which is zero for an empty catalogue.
+ */
+pub fn light_travel_time_spread(stars: &[StarData]) -> Time<f64> {
+    let distances = stars.iter().map(|star| star.get_distance_at_epoch());
+    match (
+        distances.clone().reduce(|a, b| if a < b { a } else { b }),
+        distances.reduce(|a, b| if a > b { a } else { b }),
+    ) {
+        (Some(min), Some(max)) => light_travel_time(max) - light_travel_time(min),
+        _ => TIME_ZERO,
+    }
+}
+
+/*
+ * Generates a catalogue-style name from the right ascension and declination of a position, like "AU J0645-1642".
+ * Both coordinates are truncated to full (arc)minutes, so stars that are close on the sky may share a name.
+ */
+pub fn generate_star_name(pos: &Cartesian) -> String {
+    let (right_ascension, declination) = match pos.to_direction() {
+        Ok(dir) => right_ascension_and_declination(&dir),
+        Err(_) => (ANGLE_ZERO, ANGLE_ZERO),
+    };
+    let ra_minutes = (right_ascension.to_degrees() / 15. * 60.).floor() as u32 % (24 * 60);
+    let dec_arcmin = (declination.to_degrees().abs() * 60.).floor() as u32;
+    let sign = if declination.rad < 0. { '-' } else { '+' };
+    format!(
+        "AU J{:02}{:02}{}{:02}{:02}",
+        ra_minutes / 60,
+        ra_minutes % 60,
+        sign,
+        dec_arcmin / 60,
+        dec_arcmin % 60
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{real_data::stars::all::get_many_stars, units::time::TIME_ZERO};
+    use crate::{
+        real_data::stars::all::get_many_stars,
+        tests::eq,
+        units::{
+            distance::{distance_to_light_years, DISTANCE_ZERO, LIGHT_YEAR},
+            luminous_intensity::LUMINOSITY_ZERO,
+            mass::SOLAR_MASS,
+            time::{TIME_ZERO, YEAR},
+        },
+    };
+    use astro_coords::{earth_equatorial::EarthEquatorial, ra_and_dec::*};
+
+    #[test]
+    fn clones_share_an_id_and_distinct_stars_do_not() {
+        let stars: Vec<StarData> = get_many_stars().iter().map(|s| s.to_star_data()).collect();
+        let star = stars[0].clone();
+        assert_eq!(star.id(), stars[0].id());
+
+        let mut moved = star.clone();
+        let offset = Cartesian::new(LIGHT_YEAR, DISTANCE_ZERO, DISTANCE_ZERO);
+        moved.set_pos_at_epoch(&star.pos + &offset);
+        assert_ne!(star.id(), moved.id());
+
+        assert_ne!(stars[0].id(), stars[1].id());
+    }
 
     #[test]
     fn real_stars_have_a_non_vanishing_lifetime() {
@@ -294,6 +630,143 @@ mod tests {
         }
     }
 
+    #[test]
+    fn real_stars_are_valid() {
+        for star in get_many_stars().iter().map(|s| s.to_star_data()) {
+            assert_eq!(star.validate(), Ok(()), "Star {}", star.get_name());
+        }
+    }
+
+    #[test]
+    fn separation_along_the_line_of_sight_is_the_distance_difference() {
+        let mut near = get_many_stars()[0].to_star_data();
+        let mut far = near.clone();
+        let dir = near.pos.to_direction().unwrap();
+        near.set_pos_at_epoch(dir.to_cartesian(Distance::from_lyr(4.)));
+        far.set_pos_at_epoch(dir.to_cartesian(Distance::from_lyr(10.)));
+        let separation = physical_separation(&near, &far).unwrap();
+        assert!(eq(separation.to_lyr(), 6.));
+        assert!(eq(physical_separation(&far, &near).unwrap().to_lyr(), 6.));
+    }
+
+    #[test]
+    fn light_travel_time_spread_is_that_of_the_nearest_and_farthest_star() {
+        let stars: Vec<StarData> = get_many_stars().iter().map(|s| s.to_star_data()).collect();
+        let distances: Vec<f64> = stars
+            .iter()
+            .map(|star| distance_to_light_years(&star.get_distance_at_epoch()))
+            .collect();
+        let min = distances.iter().fold(f64::INFINITY, |a, b| a.min(*b));
+        let max = distances.iter().fold(f64::NEG_INFINITY, |a, b| a.max(*b));
+        let spread = light_travel_time_spread(&stars);
+        assert!(eq(spread / YEAR, max - min));
+        assert_eq!(light_travel_time_spread(&[]), TIME_ZERO);
+    }
+
+    #[test]
+    fn magnitudes_of_sirius_match_the_catalogue() {
+        let sirius = get_many_stars()
+            .iter()
+            .map(|s| s.to_star_data())
+            .find(|star| star.get_name() == "Sirius")
+            .unwrap();
+        let absolute = sirius.get_absolute_magnitude().unwrap();
+        let apparent = sirius.get_apparent_magnitude().unwrap();
+        assert!((absolute - 1.45).abs() < 0.01);
+        // The catalogue distance is rounded to full light years.
+        assert!((apparent - -1.44).abs() < 0.15);
+    }
+
+    #[test]
+    fn dark_star_has_no_magnitude() {
+        let mut star = get_many_stars()[0].to_star_data();
+        star.set_luminous_intensity_at_epoch(LUMINOSITY_ZERO);
+        assert!(star.get_absolute_magnitude().is_none());
+        assert!(star.get_apparent_magnitude().is_none());
+    }
+
+    #[test]
+    fn strict_appearance_requires_temperature_and_distance() {
+        let star = get_many_stars()[0].to_star_data();
+        assert!(star.to_star_appearance_strict(TIME_ZERO).is_ok());
+
+        let mut without_temperature = star.clone();
+        without_temperature.set_temperature_at_epoch(Temperature::from_K(f64::NAN));
+        assert!(without_temperature
+            .to_star_appearance_strict(TIME_ZERO)
+            .is_err());
+
+        let mut without_distance = star.clone();
+        without_distance.set_pos_at_epoch(Cartesian::ORIGIN);
+        assert!(without_distance
+            .to_star_appearance_strict(TIME_ZERO)
+            .is_err());
+    }
+
+    #[test]
+    fn star_with_unknown_fields_is_displayed() {
+        let mut star = get_many_stars()[0].to_star_data();
+        star.set_mass_at_epoch(None);
+        star.set_radius_at_epoch(None);
+        star.set_age_at_epoch(None);
+        let display = star.astro_display();
+        println!("{}", display);
+        assert!(display.contains(star.get_name()));
+        assert!(display.contains("Mass: unknown"));
+        assert!(display.contains("Radius: unknown"));
+        assert!(display.contains("Age: unknown"));
+    }
+
+    #[test]
+    fn appearance_from_origin_is_the_default_appearance() {
+        for star in get_many_stars().iter().map(|s| s.to_star_data()) {
+            assert_eq!(
+                star.to_star_appearance(TIME_ZERO),
+                star.to_star_appearance_from(&Cartesian::ORIGIN, TIME_ZERO)
+            );
+        }
+    }
+
+    #[test]
+    fn nearby_star_shifts_when_observer_moves() {
+        let mut star = get_many_stars()[0].to_star_data();
+        star.set_pos_at_epoch(Cartesian::new(
+            Distance::from_lyr(4.),
+            Distance::from_lyr(0.),
+            Distance::from_lyr(0.),
+        ));
+        let observer = Cartesian::new(
+            Distance::from_lyr(0.),
+            Distance::from_lyr(1.),
+            Distance::from_lyr(0.),
+        );
+        let from_origin = star.to_star_appearance(TIME_ZERO);
+        let from_observer = star.to_star_appearance_from(&observer, TIME_ZERO);
+        let shift = from_origin.get_pos().angle_to(from_observer.get_pos());
+        assert!(shift.to_degrees() > 10.);
+        assert!(from_observer.get_illuminance() < from_origin.get_illuminance());
+    }
+
+    #[test]
+    fn negative_temperature_and_absurd_radius_are_reported() {
+        let mut star = get_many_stars()[0].to_star_data();
+        star.set_temperature_at_epoch(Temperature::from_K(-100.));
+        star.set_radius_at_epoch(Some(Distance::from_au(1e6)));
+        let problems = star.validate().unwrap_err();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("Temperature"));
+        assert!(problems[1].contains("Radius"));
+    }
+
+    #[test]
+    fn radius_without_mass_is_reported() {
+        let mut star = get_many_stars()[0].to_star_data();
+        star.set_radius_at_epoch(Some(SOLAR_RADIUS));
+        star.set_mass_at_epoch(None);
+        let problems = star.validate().unwrap_err();
+        assert_eq!(problems.len(), 1);
+    }
+
     fn kinda_equal(a: Option<f64>, b: Option<f64>) -> bool {
         match (a, b) {
             (Some(a), Some(b)) => (a - b).abs() < 1e-6,
@@ -380,4 +853,63 @@ mod tests {
             ));
         }
     }
+
+    #[test]
+    fn generated_name_of_sirius() {
+        let ra = RightAscension::new(6, 45, 9.).to_angle();
+        let dec = Declination::new(Sgn::Neg, 16, 42, 58.).to_angle();
+        let pos = EarthEquatorial::new(ra, dec)
+            .to_direction()
+            .to_cartesian(Distance::from_lyr(8.6));
+        assert_eq!(generate_star_name(&pos), "AU J0645-1642");
+    }
+
+    #[test]
+    fn generated_names_depend_only_on_the_position() {
+        let star = get_many_stars()[0].to_star_data();
+        let mut twin = star.clone();
+        twin.name = "Schnuffelpuff".to_string();
+        assert_eq!(
+            generate_star_name(star.get_pos_at_epoch()),
+            generate_star_name(twin.get_pos_at_epoch())
+        );
+
+        let other = get_many_stars()[1].to_star_data();
+        assert_ne!(
+            generate_star_name(star.get_pos_at_epoch()),
+            generate_star_name(other.get_pos_at_epoch())
+        );
+    }
+
+    #[test]
+    fn interpolation_hits_the_endpoints_and_lies_between() {
+        let stars = get_many_stars();
+        let start = stars[0].to_star_data();
+        let mut end = start.clone();
+        end.params.temperature = start.params.temperature * 2.;
+        end.params.luminous_intensity = start.params.luminous_intensity * 3.;
+        end.evolution.age = start.evolution.age.map(|age| age * 2.);
+        end.pos = stars[1].to_star_data().pos;
+
+        assert_eq!(start.interpolate(&end, 0.), start);
+        assert_eq!(start.interpolate(&end, 1.), end);
+
+        let middle = start.interpolate(&end, 0.5);
+        assert!(middle.params.temperature > start.params.temperature);
+        assert!(middle.params.temperature < end.params.temperature);
+        let distance_to_start = (&middle.pos - &start.pos).length();
+        let distance_to_end = (&middle.pos - &end.pos).length();
+        assert!(eq(distance_to_start.to_lyr(), distance_to_end.to_lyr()));
+    }
+
+    #[test]
+    fn sun_reddens_towards_the_end_of_its_life() {
+        let parsec_data = ParsecData::test_stub();
+        let blueness = |age: Time<f64>| {
+            let (r, _, b) =
+                StarData::color_at_age(SOLAR_MASS, age, &parsec_data).maximized_sRGB_tuple();
+            b / r
+        };
+        assert!(blueness(Time::from_Gyr(9.9)) < blueness(Time::from_Gyr(4.6)));
+    }
 }
@@ -16,6 +16,12 @@ pub struct StarDataEvolution {
     pub(super) fate: StarFate,
 }
 
+impl Default for StarDataEvolution {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
 impl StarDataEvolution {
     pub const NONE: StarDataEvolution = StarDataEvolution {
         lifestage_evolution: None,
@@ -14,6 +14,7 @@ use gaia_access::{
     query::GaiaQueryBuilder,
     result::{get_float, get_string, GaiaCellData, GaiaResult},
 };
+#[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use simple_si_units::{base::Temperature, electromagnetic::Illuminance, geometry::Angle};
@@ -56,9 +57,11 @@ fn get_illuminance(map: &HashMap<Col, GaiaCellData>) -> Option<Illuminance<f64>>
 }
 
 fn to_star_appearances(result: GaiaResult<Col>) -> Result<Vec<StarAppearance>, AstroUtilError> {
-    let stars = result
-        .data
-        .par_iter()
+    #[cfg(feature = "parallel")]
+    let data = result.data.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let data = result.data.iter();
+    let stars = data
         .map(|map| {
             let name =
                 get_designation(map).ok_or(AstroUtilError::DataNotAvailable("name".to_string()))?;
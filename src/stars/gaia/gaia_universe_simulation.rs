@@ -8,6 +8,7 @@ use gaia_access::{
     query::GaiaQueryBuilder,
     result::*,
 };
+#[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use simple_si_units::{
     base::{Distance, Luminosity, Mass, Temperature, Time},
@@ -73,9 +74,11 @@ fn get_evolution(map: &HashMap<Col, GaiaCellData>) -> Option<StarDataEvolution>
 }
 
 pub(crate) fn to_star_data(result: GaiaResult<Col>) -> Result<Vec<StarData>, AstroUtilError> {
-    let stars = result
-        .data
-        .par_iter()
+    #[cfg(feature = "parallel")]
+    let data = result.data.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let data = result.data.iter();
+    let stars = data
         .map(|map| {
             let name = get_id(map).ok_or(AstroUtilError::DataNotAvailable("name".to_string()))?;
 
@@ -97,6 +100,7 @@ pub(crate) fn to_star_data(result: GaiaResult<Col>) -> Result<Vec<StarData>, Ast
                 pos,
                 constellation: None,
                 evolution,
+                proper_motion: None,
             };
             Ok(star)
         })
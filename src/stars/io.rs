@@ -0,0 +1,563 @@
+use super::{
+    appearance::StarAppearance,
+    data::{StarData, StarId},
+};
+use crate::{
+    coordinates::projection::SkyProjection, error::AstroUtilError,
+    units::illuminance::illuminance_to_apparent_magnitude,
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use simple_si_units::base::Distance;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const SKY_SVG_WIDTH: u32 = 2000;
+const SKY_SVG_HEIGHT: u32 = 1000;
+const SKY_SVG_DIMMEST_MAGNITUDE: f64 = 6.5;
+const SKY_SVG_MIN_RADIUS: f64 = 0.5;
+const SKY_SVG_MAX_RADIUS: f64 = 6.;
+
+/*
+ * Increase this whenever the serialized layout of StarData changes.
+ * Fields added later need a serde default, so that files written with an older layout still load.
+ */
+pub const STAR_DATA_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializedStarData {
+    pub version: u32,
+    pub star: StarData,
+}
+
+impl SerializedStarData {
+    pub fn new(star: StarData) -> Self {
+        Self {
+            version: STAR_DATA_VERSION,
+            star,
+        }
+    }
+
+    pub fn into_star_data(self) -> Result<StarData, AstroUtilError> {
+        check_version(self.version)?;
+        Ok(self.star)
+    }
+}
+
+fn check_version(version: u32) -> Result<(), AstroUtilError> {
+    if version > STAR_DATA_VERSION {
+        return Err(AstroUtilError::DataNotAvailable(format!(
+            "StarData version {} (newest known version is {})",
+            version, STAR_DATA_VERSION
+        )));
+    }
+    Ok(())
+}
+
+/*
+ * Files written before the version tag was introduced contain bare StarData.
+ */
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredStarData {
+    Versioned(SerializedStarData),
+    Unversioned(StarData),
+}
+
+impl StoredStarData {
+    fn into_star_data(self) -> Result<StarData, AstroUtilError> {
+        match self {
+            StoredStarData::Versioned(serialized) => serialized.into_star_data(),
+            StoredStarData::Unversioned(star) => Ok(star),
+        }
+    }
+}
+
+fn from_stored(stored: Vec<StoredStarData>) -> Result<Vec<StarData>, AstroUtilError> {
+    stored
+        .into_iter()
+        .map(|star| star.into_star_data())
+        .collect()
+}
+
+/*
+ * A whole catalogue behind a single version header, which is cheaper than tagging every star.
+ * This is the layout of all JSON and MessagePack written by this module.
+ */
+#[derive(Serialize, Deserialize)]
+struct SerializedCatalog {
+    version: u32,
+    stars: Vec<StarData>,
+}
+
+impl SerializedCatalog {
+    fn new(stars: &[StarData]) -> Self {
+        Self {
+            version: STAR_DATA_VERSION,
+            stars: stars.to_vec(),
+        }
+    }
+}
+
+/*
+ * Files written before the catalogue header was introduced contain a list of stars, each with or without its own version tag.
+ */
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredCatalog {
+    WithHeader(SerializedCatalog),
+    WithoutHeader(Vec<StoredStarData>),
+}
+
+impl StoredCatalog {
+    fn into_stars(self) -> Result<Vec<StarData>, AstroUtilError> {
+        match self {
+            StoredCatalog::WithHeader(catalog) => {
+                check_version(catalog.version)?;
+                Ok(catalog.stars)
+            }
+            StoredCatalog::WithoutHeader(stored) => from_stored(stored),
+        }
+    }
+}
+
+pub fn stars_to_json(stars: &[StarData]) -> Result<String, AstroUtilError> {
+    serde_json::to_string(&SerializedCatalog::new(stars)).map_err(AstroUtilError::Json)
+}
+
+pub fn stars_from_json(json: &str) -> Result<Vec<StarData>, AstroUtilError> {
+    let stored: StoredCatalog = serde_json::from_str(json).map_err(AstroUtilError::Json)?;
+    stored.into_stars()
+}
+
+pub fn write_stars_json(stars: &[StarData], path: &Path) -> Result<(), AstroUtilError> {
+    let file = File::create(path).map_err(AstroUtilError::Io)?;
+    let writer = BufWriter::new(file);
+    serde_json::to_writer(writer, &SerializedCatalog::new(stars)).map_err(AstroUtilError::Json)
+}
+
+pub fn read_stars_json(path: &Path) -> Result<Vec<StarData>, AstroUtilError> {
+    let file = File::open(path).map_err(AstroUtilError::Io)?;
+    let reader = BufReader::new(file);
+    let stored: StoredCatalog = serde_json::from_reader(reader).map_err(AstroUtilError::Json)?;
+    stored.into_stars()
+}
+
+pub fn save_catalog(path: &Path, stars: &[StarData]) -> Result<(), AstroUtilError> {
+    write_stars_json(stars, path)
+}
+
+pub fn load_catalog(path: &Path) -> Result<Vec<StarData>, AstroUtilError> {
+    read_stars_json(path)
+}
+
+/*
+ * MessagePack is considerably more compact and faster to parse than JSON, which matters for large catalogs.
+ */
+pub fn stars_to_binary(stars: &[StarData]) -> Result<Vec<u8>, AstroUtilError> {
+    rmp_serde::to_vec(&SerializedCatalog::new(stars)).map_err(AstroUtilError::RmpSerialization)
+}
+
+pub fn stars_from_binary(bytes: &[u8]) -> Result<Vec<StarData>, AstroUtilError> {
+    let stored: StoredCatalog =
+        rmp_serde::from_slice(bytes).map_err(AstroUtilError::RmpDeserialization)?;
+    stored.into_stars()
+}
+
+pub fn write_stars_binary(stars: &[StarData], path: &Path) -> Result<(), AstroUtilError> {
+    let buffer = stars_to_binary(stars)?;
+    let file = File::create(path).map_err(AstroUtilError::Io)?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(&buffer).map_err(AstroUtilError::Io)
+}
+
+pub fn read_stars_binary(path: &Path) -> Result<Vec<StarData>, AstroUtilError> {
+    let bytes = std::fs::read(path).map_err(AstroUtilError::Io)?;
+    stars_from_binary(&bytes)
+}
+
+pub fn write_stars_gz(stars: &[StarData], path: &Path) -> Result<(), AstroUtilError> {
+    let buffer = stars_to_binary(stars)?;
+    let file = File::create(path).map_err(AstroUtilError::Io)?;
+    let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+    encoder.write_all(&buffer).map_err(AstroUtilError::Io)?;
+    encoder.finish().map_err(AstroUtilError::Io)?;
+    Ok(())
+}
+
+pub fn read_stars_gz(path: &Path) -> Result<Vec<StarData>, AstroUtilError> {
+    let file = File::open(path).map_err(AstroUtilError::Io)?;
+    let mut decoder = GzDecoder::new(BufReader::new(file));
+    let mut bytes = Vec::new();
+    decoder
+        .read_to_end(&mut bytes)
+        .map_err(AstroUtilError::Io)?;
+    stars_from_binary(&bytes)
+}
+
+/*
+ * The changes between two versions of a catalogue.
+ * Modified stars are stored as (old, new) pairs.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogDiff {
+    pub added: Vec<StarData>,
+    pub removed: Vec<StarData>,
+    pub modified: Vec<(StarData, StarData)>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/*
+ * Compares two catalogues.
+ * Stars are first matched by their id. The remaining ones are matched if their positions lie within tol of each other.
+ * Matched stars that are not identical count as modified.
+ */
+pub fn diff_catalogs(old: &[StarData], new: &[StarData], tol: Distance<f64>) -> CatalogDiff {
+    let mut old_by_id: HashMap<StarId, Vec<usize>> = HashMap::new();
+    for (i, star) in old.iter().enumerate() {
+        old_by_id.entry(star.id()).or_default().push(i);
+    }
+    let mut is_old_matched = vec![false; old.len()];
+    let mut matches = Vec::new();
+    let mut unmatched_new = Vec::new();
+    for new_star in new.iter() {
+        match old_by_id
+            .get_mut(&new_star.id())
+            .and_then(|indices| indices.pop())
+        {
+            Some(i) => {
+                is_old_matched[i] = true;
+                matches.push((i, new_star));
+            }
+            None => unmatched_new.push(new_star),
+        }
+    }
+
+    let mut diff = CatalogDiff::default();
+    for new_star in unmatched_new {
+        let closest = (0..old.len())
+            .filter(|&i| !is_old_matched[i])
+            .map(|i| (i, (&old[i].pos - &new_star.pos).length()))
+            .filter(|(_, distance)| *distance <= tol)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        match closest {
+            Some((i, _)) => {
+                is_old_matched[i] = true;
+                matches.push((i, new_star));
+            }
+            None => diff.added.push(new_star.clone()),
+        }
+    }
+    for (i, new_star) in matches {
+        if old[i] != *new_star {
+            diff.modified.push((old[i].clone(), new_star.clone()));
+        }
+    }
+    diff.removed = old
+        .iter()
+        .zip(is_old_matched)
+        .filter(|(_, is_matched)| !is_matched)
+        .map(|(star, _)| star.clone())
+        .collect();
+    diff
+}
+
+/*
+ * Plots all appearances onto a map of the whole sky.
+ * The radius of each dot grows linearly with decreasing apparent magnitude.
+ */
+pub fn sky_to_svg(appearances: &[StarAppearance], projection: SkyProjection) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        SKY_SVG_WIDTH, SKY_SVG_HEIGHT
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"black\" />\n");
+    for appearance in appearances {
+        let (x, y) = projection.project(&appearance.pos.spherical.to_direction());
+        let x = (x + 1.) * SKY_SVG_WIDTH as f64 / 2.;
+        let y = (1. - y) * SKY_SVG_HEIGHT as f64 / 2.;
+        let (r, g, b) = appearance.color.maximized_sRGB_tuple();
+        svg.push_str(&format!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"#{:02x}{:02x}{:02x}\" />\n",
+            x,
+            y,
+            sky_svg_radius(appearance),
+            to_color_byte(r),
+            to_color_byte(g),
+            to_color_byte(b)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn sky_svg_radius(appearance: &StarAppearance) -> f64 {
+    let magnitude = illuminance_to_apparent_magnitude(&appearance.illuminance);
+    let radius = SKY_SVG_MIN_RADIUS + (SKY_SVG_DIMMEST_MAGNITUDE - magnitude) * 0.5;
+    if radius.is_nan() {
+        return SKY_SVG_MIN_RADIUS;
+    }
+    radius.clamp(SKY_SVG_MIN_RADIUS, SKY_SVG_MAX_RADIUS)
+}
+
+fn to_color_byte(value: f64) -> u8 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::srgb::sRGBColor,
+        real_data::stars::{all::get_many_stars, SUN},
+        stars::{
+            evolution::StarDataEvolution,
+            random::random_stars::{generate, GenerationConfig},
+        },
+        units::{
+            distance::LIGHT_YEAR, illuminance::apparent_magnitude_to_illuminance, time::TIME_ZERO,
+        },
+    };
+    use astro_coords::{cartesian::Cartesian, ecliptic::Ecliptic};
+
+    fn sample_catalog() -> Vec<StarData> {
+        get_many_stars()
+            .iter()
+            .take(20)
+            .map(|star| star.to_star_data())
+            .collect()
+    }
+
+    fn versioned_stars(stars: &[StarData]) -> Vec<SerializedStarData> {
+        stars
+            .iter()
+            .map(|star| SerializedStarData::new(star.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let stars = sample_catalog();
+        let json = stars_to_json(&stars).unwrap();
+        let read = stars_from_json(&json).unwrap();
+        assert_eq!(read, stars);
+    }
+
+    #[test]
+    fn json_file_roundtrip() {
+        let stars = sample_catalog();
+        let path = std::env::temp_dir().join("astro_utils_json_file_roundtrip.json");
+        write_stars_json(&stars, &path).unwrap();
+        let read = read_stars_json(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, stars);
+    }
+
+    #[test]
+    fn generated_catalog_roundtrip() {
+        let config = GenerationConfig::new(Distance::from_lyr(100.)).with_seed(42);
+        let stars = generate(&config).unwrap();
+        assert!(!stars.is_empty());
+        let path = std::env::temp_dir().join("astro_utils_generated_catalog_roundtrip.json");
+        save_catalog(&path, &stars).unwrap();
+        let read = load_catalog(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, stars);
+    }
+
+    #[test]
+    fn catalog_from_a_newer_version_is_rejected() {
+        let catalog = SerializedCatalog {
+            version: STAR_DATA_VERSION + 1,
+            stars: sample_catalog(),
+        };
+        let path = std::env::temp_dir().join("astro_utils_newer_catalog.json");
+        std::fs::write(&path, serde_json::to_string(&catalog).unwrap()).unwrap();
+        let read = load_catalog(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(read.is_err());
+    }
+
+    #[test]
+    fn catalog_with_versioned_stars_still_loads() {
+        let stars = sample_catalog();
+        let path = std::env::temp_dir().join("astro_utils_versioned_stars_catalog.json");
+        std::fs::write(
+            &path,
+            serde_json::to_string(&versioned_stars(&stars)).unwrap(),
+        )
+        .unwrap();
+        let read = load_catalog(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), stars);
+    }
+
+    #[test]
+    fn binary_file_roundtrip() {
+        let stars = sample_catalog();
+        let path = std::env::temp_dir().join("astro_utils_binary_file_roundtrip.rmp");
+        write_stars_binary(&stars, &path).unwrap();
+        let read = read_stars_binary(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, stars);
+    }
+
+    #[test]
+    fn binary_with_versioned_stars_still_loads() {
+        let stars = sample_catalog();
+        let bytes = rmp_serde::to_vec(&versioned_stars(&stars)).unwrap();
+        assert_eq!(stars_from_binary(&bytes).unwrap(), stars);
+    }
+
+    #[test]
+    fn binary_from_a_newer_version_is_rejected() {
+        let catalog = SerializedCatalog {
+            version: STAR_DATA_VERSION + 1,
+            stars: sample_catalog(),
+        };
+        let bytes = rmp_serde::to_vec(&catalog).unwrap();
+        assert!(stars_from_binary(&bytes).is_err());
+    }
+
+    #[test]
+    fn binary_is_much_smaller_than_json() {
+        let stars = sample_catalog();
+        let json = stars_to_json(&stars).unwrap();
+        let binary = stars_to_binary(&stars).unwrap();
+        println!("JSON: {} bytes, binary: {} bytes", json.len(), binary.len());
+        assert!(2 * binary.len() < json.len());
+    }
+
+    #[test]
+    fn gz_file_roundtrip() {
+        let stars = sample_catalog();
+        let path = std::env::temp_dir().join("astro_utils_gz_file_roundtrip.rmp.gz");
+        write_stars_gz(&stars, &path).unwrap();
+        let read = read_stars_gz(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, stars);
+    }
+
+    #[test]
+    fn gz_file_is_smaller_than_binary_file() {
+        let stars = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_data())
+            .collect::<Vec<StarData>>();
+        let binary_path = std::env::temp_dir().join("astro_utils_uncompressed.rmp");
+        let gz_path = std::env::temp_dir().join("astro_utils_compressed.rmp.gz");
+        write_stars_binary(&stars, &binary_path).unwrap();
+        write_stars_gz(&stars, &gz_path).unwrap();
+        let binary_size = std::fs::metadata(&binary_path).unwrap().len();
+        let gz_size = std::fs::metadata(&gz_path).unwrap().len();
+        std::fs::remove_file(&binary_path).unwrap();
+        std::fs::remove_file(&gz_path).unwrap();
+        println!("binary: {} bytes, gz: {} bytes", binary_size, gz_size);
+        assert!(gz_size < binary_size);
+    }
+
+    #[test]
+    fn unversioned_star_without_evolution_loads_with_defaults() {
+        let sun = SUN.to_star_data();
+        let mut legacy = serde_json::to_value(&sun).unwrap();
+        legacy.as_object_mut().unwrap().remove("evolution").unwrap();
+        let json = serde_json::Value::Array(vec![legacy]).to_string();
+
+        let read = stars_from_json(&json).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].get_name(), sun.get_name());
+        assert_eq!(read[0].get_evolution(), &StarDataEvolution::NONE);
+    }
+
+    #[test]
+    fn star_from_a_newer_version_is_rejected() {
+        let serialized = SerializedStarData {
+            version: STAR_DATA_VERSION + 1,
+            star: SUN.to_star_data(),
+        };
+        let json = serde_json::to_string(&vec![serialized]).unwrap();
+        assert!(stars_from_json(&json).is_err());
+    }
+
+    #[test]
+    fn adding_a_star_is_reported_as_a_single_addition() {
+        let old = sample_catalog();
+        let mut new = old.clone();
+        let mut extra = SUN.to_star_data();
+        extra.set_name("Schnuffelpuff".to_string());
+        extra.set_pos_at_epoch(Cartesian::new(LIGHT_YEAR, LIGHT_YEAR, LIGHT_YEAR));
+        new.push(extra.clone());
+
+        let diff = diff_catalogs(&old, &new, LIGHT_YEAR * 0.01);
+
+        assert_eq!(diff.added, vec![extra]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.modified.is_empty());
+        assert!(diff_catalogs(&old, &old, LIGHT_YEAR * 0.01).is_empty());
+    }
+
+    #[test]
+    fn renamed_star_is_matched_by_position() {
+        let old = sample_catalog();
+        let mut new = old.clone();
+        new[3].set_name("Schnuffelpuff".to_string());
+
+        let diff = diff_catalogs(&old, &new, LIGHT_YEAR * 0.01);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.modified, vec![(old[3].clone(), new[3].clone())]);
+    }
+
+    fn circle_radii(svg: &str) -> Vec<f64> {
+        svg.lines()
+            .filter(|line| line.starts_with("<circle"))
+            .map(|line| {
+                let radius = line.split(" r=\"").nth(1).unwrap();
+                radius.split('"').next().unwrap().parse().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sky_svg_has_a_circle_per_star() {
+        let appearances = get_many_stars()
+            .iter()
+            .map(|star| star.to_star_appearance())
+            .collect::<Vec<_>>();
+        let svg = sky_to_svg(&appearances, SkyProjection::Hammer);
+        assert_eq!(svg.matches("<circle").count(), appearances.len());
+    }
+
+    #[test]
+    fn brighter_stars_have_larger_circles() {
+        let appearances = [0., 3.]
+            .iter()
+            .map(|magnitude| {
+                StarAppearance::new(
+                    "Schnuffelpuff".to_string(),
+                    apparent_magnitude_to_illuminance(*magnitude),
+                    sRGBColor::from_sRGB(1., 1., 1.),
+                    Ecliptic::X_DIRECTION,
+                    TIME_ZERO,
+                )
+            })
+            .collect::<Vec<_>>();
+        let radii = circle_radii(&sky_to_svg(&appearances, SkyProjection::Equirectangular));
+        assert_eq!(radii.len(), 2);
+        assert!(radii[0] > radii[1]);
+    }
+}
@@ -1,9 +1,16 @@
 pub mod appearance;
+pub mod color_magnitude;
 pub mod constellation;
 pub mod data;
 pub mod evolution;
 pub mod fate;
 pub mod gaia;
+pub mod io;
+pub mod parallax;
 pub mod physical_parameters;
+pub mod proper_motion;
 pub mod random;
 pub mod real_data;
+pub mod sky_brightness;
+pub mod substellar;
+pub mod system;
@@ -0,0 +1,43 @@
+use astro_coords::cartesian::Cartesian;
+use simple_si_units::{base::Time, geometry::Angle};
+
+use super::data::StarData;
+use crate::error::AstroUtilError;
+
+/*
+ * The angle by which the apparent position of the star shifts between two vantage points.
+ */
+pub fn parallax_angle(
+    star: &StarData,
+    observer1: &Cartesian,
+    observer2: &Cartesian,
+    time_since_epoch: Time<f64>,
+) -> Result<Angle<f64>, AstroUtilError> {
+    let star_pos = star.get_pos(time_since_epoch);
+    let seen_from_1 = &star_pos - observer1;
+    let seen_from_2 = &star_pos - observer2;
+    Ok(seen_from_1.angle_to(&seen_from_2)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use astro_coords::direction::Direction;
+    use simple_si_units::base::Distance;
+
+    use super::*;
+    use crate::{
+        real_data::stars::SUN,
+        tests::eq_within,
+        units::{angle::angle_to_arcsecs, time::TIME_ZERO},
+    };
+
+    #[test]
+    fn star_at_one_parsec_across_two_au() {
+        let mut star = SUN.to_star_data();
+        star.set_pos_at_epoch(Direction::Z.to_cartesian(Distance::from_parsec(1.)));
+        let observer1 = Direction::X.to_cartesian(Distance::from_au(1.));
+        let observer2 = Direction::X.to_cartesian(Distance::from_au(-1.));
+        let parallax = parallax_angle(&star, &observer1, &observer2, TIME_ZERO).unwrap();
+        assert!(eq_within(angle_to_arcsecs(&parallax), 2., 0.01));
+    }
+}
@@ -0,0 +1,127 @@
+use astro_coords::{cartesian::Cartesian, direction::Direction};
+use serde::{Deserialize, Serialize};
+use simple_si_units::{
+    base::{Distance, Time},
+    geometry::Angle,
+    mechanical::Velocity,
+};
+
+use crate::{
+    coordinates::{
+        earth_equatorial::{ecliptic_to_equatorial, equatorial_to_ecliptic},
+        projection::tangent_basis,
+    },
+    error::AstroUtilError,
+    units::time::YEAR,
+};
+
+/*
+ * The motion of a star through space, given by the angles its right ascension and declination change by per Julian year, and its velocity along the line of sight.
+ * Like in the Hipparcos and Gaia catalogues, the rate in right ascension already contains the factor cos(declination), so it is the actual angle covered on the sky.
+ * The star is assumed to move in a straight line at constant speed.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProperMotion {
+    pub(super) right_ascension_per_year: Angle<f64>,
+    pub(super) declination_per_year: Angle<f64>,
+    pub(super) radial_velocity: Velocity<f64>,
+}
+
+impl ProperMotion {
+    pub fn new(
+        right_ascension_per_year: Angle<f64>,
+        declination_per_year: Angle<f64>,
+        radial_velocity: Velocity<f64>,
+    ) -> Self {
+        Self {
+            right_ascension_per_year,
+            declination_per_year,
+            radial_velocity,
+        }
+    }
+
+    pub fn get_right_ascension_per_year(&self) -> Angle<f64> {
+        self.right_ascension_per_year
+    }
+
+    pub fn get_declination_per_year(&self) -> Angle<f64> {
+        self.declination_per_year
+    }
+
+    pub fn get_radial_velocity(&self) -> Velocity<f64> {
+        self.radial_velocity
+    }
+
+    /*
+     * The position a star at pos has moved to after the given time.
+     * A star at the origin has no direction to move along, so it stays where it is.
+     */
+    pub(super) fn apply(&self, pos: &Cartesian, time: Time<f64>) -> Cartesian {
+        self.displacement(pos, time)
+            .map(|displacement| pos + &displacement)
+            .unwrap_or_else(|_| pos.clone())
+    }
+
+    fn displacement(&self, pos: &Cartesian, time: Time<f64>) -> Result<Cartesian, AstroUtilError> {
+        let dir = pos.to_direction()?;
+        let [x, y, z] = ecliptic_to_equatorial(&dir);
+        let (east, north) = tangent_basis(&Direction::new(x, y, z)?);
+        let east = equatorial_to_ecliptic(east)?;
+        let north = equatorial_to_ecliptic(north)?;
+        let tangential_per_radian = time / YEAR * pos.length();
+        let along_east = self.right_ascension_per_year.rad * tangential_per_radian;
+        let along_north = self.declination_per_year.rad * tangential_per_radian;
+        let along_line_of_sight: Distance<f64> = self.radial_velocity * time;
+        Ok(
+            &(&east.to_cartesian(along_east) + &north.to_cartesian(along_north))
+                + &dir.to_cartesian(along_line_of_sight),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::earth_equatorial::earth_equatorial_to_direction_at,
+        real_data::stars::SUN,
+        stars::data::StarData,
+        units::{angle::angle_to_arcsecs, time::TIME_ZERO},
+    };
+
+    fn barnards_star() -> StarData {
+        let dir = earth_equatorial_to_direction_at(
+            Angle::from_degrees(269.452),
+            Angle::from_degrees(4.693),
+            TIME_ZERO,
+        )
+        .unwrap();
+        let mut star = SUN.to_star_data();
+        star.set_pos_at_epoch(dir.to_cartesian(Distance::from_lyr(5.96)));
+        star
+    }
+
+    #[test]
+    fn barnards_star_moves_by_ten_arcseconds_per_year() {
+        let mut star = barnards_star();
+        star.set_proper_motion(Some(ProperMotion::new(
+            Angle::from_degrees(-0.79858 / 3600.),
+            Angle::from_degrees(10.32812 / 3600.),
+            Velocity::from_kmps(-110.6),
+        )));
+        let now = star.position_at_time(TIME_ZERO).unwrap();
+        let in_a_century = star.position_at_time(Time::from_yr(100.)).unwrap();
+        let shift = angle_to_arcsecs(&now.angle_to(&in_a_century));
+        // The star approaches, so it appears to speed up slightly.
+        assert!(shift > 1036.);
+        assert!(shift < 1045.);
+    }
+
+    #[test]
+    fn star_without_proper_motion_stays_fixed() {
+        let star = barnards_star();
+        let now = star.position_at_time(TIME_ZERO).unwrap();
+        let later = star.position_at_time(Time::from_yr(1e4)).unwrap();
+        assert!(now.eq_within(&later, 1e-12));
+    }
+}
@@ -0,0 +1,118 @@
+use astro_coords::cartesian::Cartesian;
+use simple_si_units::base::{Distance, Time};
+
+use crate::{
+    coordinates::galactic::galactic_latitude,
+    stars::{appearance::StarAppearance, data::StarData},
+    units::illuminance::scaled_illuminance,
+};
+
+/*
+ * Dimming by interstellar dust, which is concentrated in a thin layer around the galactic plane.
+ * The dust density falls off exponentially with the height above the plane, in which the observer is assumed to sit.
+ * Integrating along the line of sight yields the cosecant law:
+ * https://en.wikipedia.org/wiki/Extinction_(astronomy)#Galactic_extinction
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GalacticExtinction {
+    magnitudes_per_kpc: f64,
+    scale_height: Distance<f64>,
+}
+
+impl GalacticExtinction {
+    pub fn new(magnitudes_per_kpc: f64, scale_height: Distance<f64>) -> Self {
+        Self {
+            magnitudes_per_kpc,
+            scale_height,
+        }
+    }
+
+    pub fn get_magnitudes_per_kpc(&self) -> f64 {
+        self.magnitudes_per_kpc
+    }
+
+    pub fn get_scale_height(&self) -> Distance<f64> {
+        self.scale_height
+    }
+
+    /*
+     * The extinction in magnitudes for light reaching the origin from pos.
+     */
+    pub fn extinction_in_magnitudes(&self, pos: &Cartesian) -> f64 {
+        let distance = pos.length();
+        let sin_latitude = match pos.to_direction() {
+            Ok(dir) => galactic_latitude(&dir).rad.sin().abs(),
+            Err(_) => return 0.,
+        };
+        let heights = distance * sin_latitude / self.scale_height;
+        // The fraction of the path through the dust compared to a path along the plane.
+        let fraction = if heights > 0. {
+            -(-heights).exp_m1() / heights
+        } else {
+            1.
+        };
+        self.magnitudes_per_kpc * distance.to_parsec() / 1_000. * fraction
+    }
+
+    /*
+     * The factor by which the illuminance of a star at pos is reduced.
+     */
+    pub fn transmission(&self, pos: &Cartesian) -> f64 {
+        10f64.powf(-0.4 * self.extinction_in_magnitudes(pos))
+    }
+
+    /*
+     * Like StarData::to_star_appearance, but dimmed by the dust between the star and the origin.
+     */
+    pub fn to_star_appearance(
+        &self,
+        star: &StarData,
+        time_since_epoch: Time<f64>,
+    ) -> StarAppearance {
+        let mut appearance = star.to_star_appearance(time_since_epoch);
+        let transmission = self.transmission(&star.get_pos(time_since_epoch));
+        appearance.illuminance = scaled_illuminance(&appearance.illuminance, transmission);
+        appearance
+    }
+}
+
+/*
+ * Typical values for visible light in the solar neighbourhood.
+ */
+impl Default for GalacticExtinction {
+    fn default() -> Self {
+        Self::new(1., Distance::from_parsec(100.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::{
+            galactic::north_galactic_pole,
+            projection::{components, cross},
+        },
+        tests::eq,
+    };
+    use astro_coords::direction::Direction;
+
+    #[test]
+    fn extinction_through_the_plane_grows_linearly() {
+        let extinction = GalacticExtinction::default();
+        let [x, y, z] = cross(&components(&north_galactic_pole()), &[0., 0., 1.]);
+        let in_plane = Direction::new(x, y, z).unwrap();
+        for kpc in [0.1, 1., 2.] {
+            let pos = in_plane.to_cartesian(Distance::from_parsec(kpc * 1_000.));
+            assert!(eq(extinction.extinction_in_magnitudes(&pos), kpc));
+        }
+    }
+
+    #[test]
+    fn extinction_towards_the_pole_saturates() {
+        let extinction = GalacticExtinction::default();
+        let pole = north_galactic_pole().to_cartesian(Distance::from_parsec(10_000.));
+        let towards_pole = extinction.extinction_in_magnitudes(&pole);
+        assert!(eq(towards_pole, 0.1));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod extinction;
 mod params;
-mod parsec;
+pub mod parsec;
 pub mod random_stars;
@@ -6,7 +6,7 @@ use crate::units::distance::DISTANCE_ZERO;
 use super::{
     parsec::data::ParsecData,
     random_stars::{
-        number_in_sphere, AGE_OF_MILKY_WAY_THIN_DISK, DIMMEST_ILLUMINANCE,
+        excluded_radius, number_in_sphere, AGE_OF_MILKY_WAY_THIN_DISK, DIMMEST_ILLUMINANCE,
         NUMBER_OF_STARS_FORMED_IN_NURSERY, STARS_PER_LY_CUBED, STELLAR_VELOCITY,
     },
 };
@@ -15,6 +15,7 @@ pub(super) struct GenerationParams {
     pub(super) pos: Cartesian,
     pub(super) max_age: Time<f64>,
     pub(super) radius: Distance<f64>,
+    pub(super) min_distance: Distance<f64>,
     pub(super) number: usize,
 }
 
@@ -28,6 +29,7 @@ impl GenerationParams {
             pos,
             max_age,
             radius,
+            min_distance: DISTANCE_ZERO,
             number,
         }
     }
@@ -39,6 +41,7 @@ impl GenerationParams {
             pos,
             max_age,
             radius,
+            min_distance: DISTANCE_ZERO,
             number,
         }
     }
@@ -62,6 +65,19 @@ impl GenerationParams {
         }
         self.number = (self.number as f64 * (self.radius / original_radius).powi(3)) as usize;
     }
+
+    /*
+     * Discards all stars closer to the origin than min_distance.
+     * The part of the sphere that lies entirely within min_distance is not sampled at all, so the number of stars is reduced accordingly.
+     */
+    pub(super) fn exclude_stars_closer_than(&mut self, min_distance: Distance<f64>) {
+        self.min_distance = min_distance;
+        if self.radius.m <= 0. {
+            return;
+        }
+        let inner = excluded_radius(&self.pos, min_distance).min(self.radius);
+        self.number = (self.number as f64 * (1. - (inner / self.radius).powi(3))) as usize;
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +120,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn excluding_half_the_radius_keeps_seven_eighths_of_the_old_stars() {
+        let mut params = GenerationParams::old_stars(Distance::from_lyr(100.));
+        let number = params.number;
+        params.exclude_stars_closer_than(Distance::from_lyr(50.));
+        assert!(eq_within(
+            params.number as f64 / number as f64,
+            7. / 8.,
+            1e-3
+        ));
+    }
+
     #[test]
     fn old_stars_far_away_are_adjusted() {
         let max_age = AGE_OF_MILKY_WAY_THIN_DISK;
@@ -2,18 +2,62 @@ use super::trajectory::Trajectory;
 use crate::error::AstroUtilError;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 lazy_static! {
-    pub(crate) static ref PARSEC_DATA: Mutex<Result<ParsecData, AstroUtilError>> =
-        Mutex::new(ParsecData::new());
+    pub(crate) static ref PARSEC_DATA: Mutex<Result<Arc<ParsecData>, AstroUtilError>> =
+        Mutex::new(ParsecData::new().map(Arc::new));
 }
 
 #[derive(Deserialize, Serialize)]
-pub(crate) struct ParsecData {
+pub struct ParsecData {
     pub(super) data: Vec<Trajectory>,
 }
 
+#[cfg(test)]
+impl ParsecData {
+    /*
+     * A small synthetic dataset that follows rough main sequence scaling relations.
+     * It allows testing the logic that depends on PARSEC data without downloading it.
+     * A star of 1 solar mass has solar parameters at 4.6 billion years.
+     */
+    pub(crate) fn test_stub() -> Self {
+        use super::line::ParsedParsecLine;
+
+        let data = Self::SORTED_MASSES
+            .iter()
+            .map(|&mass| {
+                let lifetime_in_years = (1e10 * mass.powf(-2.5)).max(3e6);
+                let main_sequence_luminous_intensity = mass.powf(3.5);
+                let main_sequence_temperature = 5778. * mass.powf(0.5);
+                let stages = [(1e-3, 0.7, 0.97), (0.46, 1., 1.), (1., 2., 0.6)];
+                let params = stages
+                    .iter()
+                    .map(
+                        |&(age_fraction, luminous_intensity_factor, temperature_factor)| {
+                            let luminous_intensity_in_solar =
+                                luminous_intensity_factor * main_sequence_luminous_intensity;
+                            let temperature_in_kelvin =
+                                temperature_factor * main_sequence_temperature;
+                            let radius_in_solar_radii = luminous_intensity_in_solar.sqrt()
+                                / (temperature_in_kelvin / 5778.).powi(2);
+                            ParsedParsecLine {
+                                mass_in_solar_masses: mass,
+                                age_in_years: age_fraction * lifetime_in_years,
+                                luminous_intensity_in_solar,
+                                temperature_in_kelvin,
+                                radius_in_solar_radii,
+                            }
+                        },
+                    )
+                    .collect();
+                Trajectory::new(params)
+            })
+            .collect();
+        ParsecData { data }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use astro_coords::cartesian::Cartesian;
@@ -29,6 +73,28 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_stub_is_filled() {
+        let parsec_data = ParsecData::test_stub();
+        assert!(parsec_data.is_filled());
+    }
+
+    #[test]
+    fn test_stub_contains_the_sun() {
+        let parsec_data = ParsecData::test_stub();
+        let mass_index = ParsecData::get_closest_mass_index(SUN.mass.to_solar_mass());
+        let calculated_sun = parsec_data
+            .get_trajectory_via_index(mass_index)
+            .to_star(SUN.age.unwrap(), Cartesian::ORIGIN);
+        let real_sun = SUN.to_star_data();
+        assert!(calculated_sun.similar_within_order_of_magnitude(&real_sun));
+        assert!(eq_within(
+            calculated_sun.get_temperature_at_epoch().K,
+            real_sun.get_temperature_at_epoch().K,
+            1.
+        ));
+    }
+
     #[test]
     fn test_caluclate_sun() {
         let mass = SUN.mass;
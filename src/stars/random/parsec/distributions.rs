@@ -1,7 +1,7 @@
 use crate::error::AstroUtilError;
 
 use super::data::ParsecData;
-use rand::{distributions::Distribution, rngs::ThreadRng};
+use rand::{distributions::Distribution, Rng};
 use rand_distr::WeightedAliasIndex;
 
 const MIN_MASS_FOR_HYDROGEN_FUSION: f64 = 0.08;
@@ -16,7 +16,7 @@ impl ParsecDistribution {
         Ok(ParsecDistribution { mass_distribution })
     }
 
-    pub(crate) fn get_random_mass_index(&self, rng: &mut ThreadRng) -> usize {
+    pub(crate) fn get_random_mass_index<R: Rng>(&self, rng: &mut R) -> usize {
         self.mass_distribution.sample(rng)
     }
 }
@@ -85,6 +85,7 @@ fn integrate_kroupa(lower: f64, upper: f64) -> f64 {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "parallel")]
     use rayon::iter::{IntoParallelIterator, ParallelIterator};
     use simple_si_units::base::Distance;
 
@@ -164,8 +165,11 @@ mod tests {
         let num_stars = number_in_sphere(STARS_PER_LY_CUBED, max_distance);
         println!("Number of stars: {}", num_stars);
         let distribution = get_mass_distribution().unwrap();
-        let num_supermassive_stars = (0..num_stars)
-            .into_par_iter()
+        #[cfg(feature = "parallel")]
+        let samples = (0..num_stars).into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let samples = 0..num_stars;
+        let num_supermassive_stars = samples
             .map(|_| {
                 let mut rng = rand::thread_rng();
                 ParsecData::SORTED_MASSES[distribution.sample(&mut rng)]
@@ -0,0 +1,161 @@
+use simple_si_units::base::{Luminosity, Mass, Temperature, Time};
+
+use crate::units::luminous_intensity::SOLAR_LUMINOUS_INTENSITY;
+
+use super::{data::ParsecData, line::ParsedParsecLine};
+
+/*
+ * Distance in the Hertzsprung-Russell diagram, measured in orders of magnitude of luminous intensity and temperature.
+ */
+fn hr_distance_squared(
+    params: &ParsedParsecLine,
+    log_luminous_intensity: f64,
+    log_temperature: f64,
+) -> f64 {
+    let d_luminous_intensity = params.luminous_intensity_in_solar.log10() - log_luminous_intensity;
+    let d_temperature = params.temperature_in_kelvin.log10() - log_temperature;
+    d_luminous_intensity.powi(2) + d_temperature.powi(2)
+}
+
+fn hr_coordinates(
+    luminous_intensity: Luminosity<f64>,
+    temperature: Temperature<f64>,
+) -> Option<(f64, f64)> {
+    let log_luminous_intensity = (luminous_intensity / SOLAR_LUMINOUS_INTENSITY).log10();
+    let log_temperature = temperature.K.log10();
+    if log_luminous_intensity.is_finite() && log_temperature.is_finite() {
+        Some((log_luminous_intensity, log_temperature))
+    } else {
+        None
+    }
+}
+
+impl ParsecData {
+    /*
+     * Returns the initial mass of the model whose trajectory passes closest to the given point in the HR diagram.
+     */
+    pub fn estimate_mass(
+        &self,
+        luminous_intensity: Luminosity<f64>,
+        temperature: Temperature<f64>,
+    ) -> Option<Mass<f64>> {
+        let (log_luminous_intensity, log_temperature) =
+            hr_coordinates(luminous_intensity, temperature)?;
+        self.data
+            .iter()
+            .filter_map(|trajectory| {
+                trajectory
+                    .get_params()
+                    .iter()
+                    .map(|params| {
+                        hr_distance_squared(params, log_luminous_intensity, log_temperature)
+                    })
+                    .filter(|distance| distance.is_finite())
+                    .min_by(|a, b| a.total_cmp(b))
+                    .map(|distance| (distance, trajectory.initial_mass))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, mass)| mass)
+    }
+
+    /*
+     * Walks the trajectory of the model closest to the given mass and returns the age at which it comes closest to the given point in the HR diagram.
+     */
+    pub fn estimate_age(
+        &self,
+        mass: Mass<f64>,
+        luminous_intensity: Luminosity<f64>,
+        temperature: Temperature<f64>,
+    ) -> Option<Time<f64>> {
+        let (log_luminous_intensity, log_temperature) =
+            hr_coordinates(luminous_intensity, temperature)?;
+        let mass_index = Self::try_get_closest_mass_index(mass).ok()?;
+        self.data
+            .get(mass_index)?
+            .get_params()
+            .iter()
+            .map(|params| {
+                let distance = hr_distance_squared(params, log_luminous_intensity, log_temperature);
+                (distance, params.age_in_years)
+            })
+            .filter(|(distance, _)| distance.is_finite())
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, age_in_years)| Time::from_yr(age_in_years))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{real_data::stars::SUN, tests::eq_within};
+
+    #[test]
+    fn sun_has_one_solar_mass() {
+        let parsec_data = ParsecData::test_stub();
+        let sun = SUN.to_star_data();
+        let mass = parsec_data
+            .estimate_mass(
+                sun.get_luminous_intensity_at_epoch(),
+                sun.get_temperature_at_epoch(),
+            )
+            .unwrap();
+        assert!(eq_within(mass.to_solar_mass(), 1., 0.05));
+    }
+
+    #[test]
+    fn brighter_and_hotter_star_is_more_massive() {
+        let parsec_data = ParsecData::test_stub();
+        let sun_like = parsec_data
+            .estimate_mass(SOLAR_LUMINOUS_INTENSITY, Temperature::from_K(5778.))
+            .unwrap();
+        let massive = parsec_data
+            .estimate_mass(
+                1e3 * SOLAR_LUMINOUS_INTENSITY,
+                Temperature::from_K(5778. * 7f64.sqrt()),
+            )
+            .unwrap();
+        assert!(massive > sun_like);
+    }
+
+    #[test]
+    fn sun_is_about_four_and_a_half_billion_years_old() {
+        let parsec_data = ParsecData::test_stub();
+        let sun = SUN.to_star_data();
+        let age = parsec_data
+            .estimate_age(
+                sun.get_mass_at_epoch().unwrap(),
+                sun.get_luminous_intensity_at_epoch(),
+                sun.get_temperature_at_epoch(),
+            )
+            .unwrap();
+        assert!(eq_within(age.to_Gyr(), 4.6, 0.5));
+    }
+
+    #[test]
+    fn bright_cool_sun_is_old() {
+        let parsec_data = ParsecData::test_stub();
+        let sun = SUN.to_star_data();
+        let young = parsec_data
+            .estimate_age(
+                sun.get_mass_at_epoch().unwrap(),
+                0.7 * SOLAR_LUMINOUS_INTENSITY,
+                Temperature::from_K(5600.),
+            )
+            .unwrap();
+        let old = parsec_data
+            .estimate_age(
+                sun.get_mass_at_epoch().unwrap(),
+                2. * SOLAR_LUMINOUS_INTENSITY,
+                Temperature::from_K(3500.),
+            )
+            .unwrap();
+        assert!(young < old);
+    }
+
+    #[test]
+    fn non_positive_temperature_has_no_estimate() {
+        let parsec_data = ParsecData::test_stub();
+        let mass = parsec_data.estimate_mass(SOLAR_LUMINOUS_INTENSITY, Temperature::from_K(0.));
+        assert!(mass.is_none());
+    }
+}
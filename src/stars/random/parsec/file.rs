@@ -3,22 +3,47 @@ use super::line::ParsecLine;
 use crate::error::AstroUtilError;
 use crate::stars::random::parsec::trajectory::Trajectory;
 use directories::ProjectDirs;
+#[cfg(feature = "download")]
 use flate2::read::GzDecoder;
 use rmp_serde;
+use std::ffi::OsString;
 use std::fs;
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "download")]
+use std::thread;
+#[cfg(feature = "download")]
+use std::time::Duration;
+#[cfg(feature = "download")]
 use tar::Archive;
 
+#[cfg(feature = "download")]
+pub(super) const DOWNLOAD_ATTEMPTS: u32 = 4;
+#[cfg(feature = "download")]
+pub(super) const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
 impl ParsecData {
     const METALLICITY: &'static str = "Z0.01";
-    const FILENAME: &'static str = "Z0.01.rmp";
+    pub const DATA_DIR_ENV_VAR: &'static str = "ASTRO_UTILS_DATA_DIR";
+    /*
+     * A comma-separated list of base URLs that are tried before the default mirror.
+     */
+    #[cfg(feature = "download")]
+    pub const MIRRORS_ENV_VAR: &'static str = "ASTRO_UTILS_PARSEC_MIRRORS";
+    #[cfg(feature = "download")]
+    const DEFAULT_MIRROR: &'static str =
+        "https://people.sissa.it/~sbressan/CAF09_V1.2S_M36_LT/no_phase/";
+
+    pub fn new() -> Result<ParsecData, AstroUtilError> {
+        Self::new_in(get_data_dir()?, Self::METALLICITY)
+    }
 
-    pub(super) fn new() -> Result<ParsecData, AstroUtilError> {
-        let project_dirs = get_project_dirs()?;
-        let data_dir = project_dirs.data_dir();
-        let file_path = data_dir.join(Self::FILENAME);
+    /*
+     * Reads (and if necessary downloads) the PARSEC data for the given metallicity, e.g. "Z0.01", into data_dir.
+     */
+    pub fn new_in(data_dir: PathBuf, metallicity: &str) -> Result<ParsecData, AstroUtilError> {
+        let file_path = data_dir.join(format!("{}.rmp", metallicity));
 
         if file_path.exists() {
             println!("Reading PARSEC data from {}", file_path.display());
@@ -31,8 +56,8 @@ impl ParsecData {
                 Err(AstroUtilError::DataNotAvailable("Parsec Data".to_string()))
             }
         } else {
-            Self::ensure_data_files()?;
-            let folder_path = data_dir.join(PathBuf::from(Self::METALLICITY));
+            Self::ensure_data_files(&data_dir, metallicity)?;
+            let folder_path = data_dir.join(PathBuf::from(metallicity));
             let filepaths = fs::read_dir(folder_path).map_err(AstroUtilError::Io)?;
             let mut parsec_data = ParsecData {
                 data: Vec::with_capacity(Self::SORTED_MASSES.len()),
@@ -57,32 +82,66 @@ impl ParsecData {
         }
     }
 
-    fn download() -> Result<(), AstroUtilError> {
-        let project_dirs = get_project_dirs()?;
-        let data_dir = project_dirs.data_dir();
-        let data_dir = data_dir
-            .to_str()
-            .ok_or(AstroUtilError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Could not convert data dir to string",
-            )))?;
-        println!("Downloading PARSEC data to {}", data_dir);
-        let target = "https://people.sissa.it/~sbressan/CAF09_V1.2S_M36_LT/no_phase/".to_string()
-            + Self::METALLICITY
-            + ".tar.gz";
-        let mut response = reqwest::blocking::get(target).map_err(AstroUtilError::Connection)?;
+    /*
+     * Reads PARSEC data that has been loaded by other means, e.g. fetched by a browser.
+     * The bytes are expected in the same format as the .rmp file in the data directory.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<ParsecData, AstroUtilError> {
+        let parsec_data: ParsecData =
+            rmp_serde::from_slice(bytes).map_err(AstroUtilError::RmpDeserialization)?;
+        if parsec_data.is_filled() {
+            Ok(parsec_data)
+        } else {
+            Err(AstroUtilError::DataNotAvailable("Parsec Data".to_string()))
+        }
+    }
+
+    #[cfg(feature = "download")]
+    pub(super) fn download_url(mirror: &str, metallicity: &str) -> String {
+        let mirror = mirror.trim_end_matches('/');
+        format!("{}/{}.tar.gz", mirror, metallicity)
+    }
+
+    #[cfg(feature = "download")]
+    fn download(data_dir: &Path, metallicity: &str) -> Result<(), AstroUtilError> {
+        fs::create_dir_all(data_dir).map_err(AstroUtilError::Io)?;
+        println!("Downloading PARSEC data to {}", data_dir.display());
+        let mut response = try_mirrors(&get_mirrors(), |mirror| {
+            let target = Self::download_url(mirror, metallicity);
+            retry_with_backoff(DOWNLOAD_ATTEMPTS, INITIAL_RETRY_DELAY, || {
+                reqwest::blocking::get(target.as_str()).and_then(|r| r.error_for_status())
+            })
+        })
+        .ok_or(AstroUtilError::DataNotAvailable(
+            "PARSEC download mirror".to_string(),
+        ))?
+        .map_err(AstroUtilError::Connection)?;
         let gz_decoder = GzDecoder::new(&mut response);
         let mut archive = Archive::new(gz_decoder);
         archive.unpack(data_dir).map_err(AstroUtilError::Io)?;
         Ok(())
     }
 
-    fn ensure_data_files() -> Result<(), AstroUtilError> {
-        let project_dirs = get_project_dirs()?;
-        let data_dir = project_dirs.data_dir();
-        let path = data_dir.join(PathBuf::from(Self::METALLICITY));
+    #[cfg(feature = "download")]
+    fn ensure_data_files(data_dir: &Path, metallicity: &str) -> Result<(), AstroUtilError> {
+        let path = data_dir.join(PathBuf::from(metallicity));
         if !path.exists() {
-            Self::download()?;
+            Self::download(data_dir, metallicity)?;
+        }
+        Ok(())
+    }
+
+    /*
+     * Without the download feature, the data files have to be supplied by the user.
+     */
+    #[cfg(not(feature = "download"))]
+    fn ensure_data_files(data_dir: &Path, metallicity: &str) -> Result<(), AstroUtilError> {
+        let path = data_dir.join(PathBuf::from(metallicity));
+        if !path.exists() {
+            return Err(AstroUtilError::DataNotAvailable(format!(
+                "PARSEC data in {}",
+                path.display()
+            )));
         }
         Ok(())
     }
@@ -107,8 +166,239 @@ impl ParsecData {
     }
 }
 
+/*
+ * Calls f up to max_attempts times, doubling the waiting time after each failure.
+ * If all attempts fail, the last error is returned.
+ */
+#[cfg(feature = "download")]
+fn retry_with_backoff<T, E, F>(max_attempts: u32, initial_delay: Duration, mut f: F) -> Result<T, E>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    let mut delay = initial_delay;
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt >= max_attempts => return Err(err),
+            Err(_) => {
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/*
+ * Calls f for each mirror in turn until one succeeds.
+ * Returns the last error if all of them fail, and None if there are no mirrors.
+ */
+#[cfg(feature = "download")]
+fn try_mirrors<T, E, F>(mirrors: &[String], mut f: F) -> Option<Result<T, E>>
+where
+    F: FnMut(&str) -> Result<T, E>,
+{
+    let mut last_error = None;
+    for mirror in mirrors {
+        match f(mirror) {
+            Ok(value) => return Some(Ok(value)),
+            Err(err) => last_error = Some(err),
+        }
+    }
+    last_error.map(Err)
+}
+
+#[cfg(feature = "download")]
+pub(super) fn get_mirrors() -> Vec<String> {
+    mirrors_with_override(std::env::var_os(ParsecData::MIRRORS_ENV_VAR))
+}
+
+#[cfg(feature = "download")]
+fn mirrors_with_override(mirrors_override: Option<OsString>) -> Vec<String> {
+    let mut mirrors: Vec<String> = mirrors_override
+        .map(|mirrors| {
+            mirrors
+                .to_string_lossy()
+                .split(',')
+                .map(|mirror| mirror.trim().to_string())
+                .filter(|mirror| !mirror.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    mirrors.push(ParsecData::DEFAULT_MIRROR.to_string());
+    mirrors
+}
+
+fn get_data_dir() -> Result<PathBuf, AstroUtilError> {
+    data_dir_with_override(std::env::var_os(ParsecData::DATA_DIR_ENV_VAR))
+}
+
+fn data_dir_with_override(data_dir_override: Option<OsString>) -> Result<PathBuf, AstroUtilError> {
+    match data_dir_override {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(get_project_dirs()?.data_dir().to_path_buf()),
+    }
+}
+
 fn get_project_dirs() -> Result<ProjectDirs, AstroUtilError> {
     ProjectDirs::from("", "the_comamba", "astro_utils").ok_or(AstroUtilError::Io(
         std::io::Error::new(std::io::ErrorKind::Other, "Could not get project dirs"),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "download")]
+    #[test]
+    fn retry_succeeds_on_second_attempt() {
+        let mut calls = 0;
+        let result = retry_with_backoff(3, Duration::from_millis(1), || {
+            calls += 1;
+            if calls < 2 {
+                Err("Connection reset")
+            } else {
+                Ok("PARSEC archive")
+            }
+        });
+        assert_eq!(result, Ok("PARSEC archive"));
+        assert_eq!(calls, 2);
+    }
+
+    #[cfg(feature = "download")]
+    #[test]
+    fn retry_returns_last_error_after_all_attempts() {
+        let mut calls = 0;
+        let result: Result<(), u32> = retry_with_backoff(3, Duration::from_millis(1), || {
+            calls += 1;
+            Err(calls)
+        });
+        assert_eq!(result, Err(3));
+        assert_eq!(calls, 3);
+    }
+
+    #[cfg(feature = "download")]
+    #[test]
+    fn second_mirror_is_used_when_first_fails() {
+        let mirrors = vec![
+            "https://broken.example.com".to_string(),
+            "https://working.example.com".to_string(),
+        ];
+        let mut tried = vec![];
+        let result = try_mirrors(&mirrors, |mirror| {
+            tried.push(mirror.to_string());
+            if mirror.contains("broken") {
+                Err("Timeout")
+            } else {
+                Ok(ParsecData::download_url(mirror, "Z0.01"))
+            }
+        });
+        assert_eq!(
+            result,
+            Some(Ok("https://working.example.com/Z0.01.tar.gz".to_string()))
+        );
+        assert_eq!(tried, mirrors);
+    }
+
+    #[cfg(feature = "download")]
+    #[test]
+    fn last_error_is_returned_when_all_mirrors_fail() {
+        let mirrors = vec!["first".to_string(), "second".to_string()];
+        let result: Option<Result<(), String>> =
+            try_mirrors(&mirrors, |mirror| Err(mirror.to_string()));
+        assert_eq!(result, Some(Err("second".to_string())));
+    }
+
+    #[cfg(feature = "download")]
+    #[test]
+    fn configured_mirrors_are_tried_before_default() {
+        let mirrors = mirrors_with_override(Some(OsString::from(
+            "https://cache.example.com/parsec/, https://other.example.com",
+        )));
+        assert_eq!(
+            mirrors,
+            vec![
+                "https://cache.example.com/parsec/".to_string(),
+                "https://other.example.com".to_string(),
+                ParsecData::DEFAULT_MIRROR.to_string(),
+            ]
+        );
+    }
+
+    #[cfg(feature = "download")]
+    #[test]
+    fn default_mirror_url_is_unchanged() {
+        assert_eq!(
+            ParsecData::download_url(ParsecData::DEFAULT_MIRROR, "Z0.01"),
+            "https://people.sissa.it/~sbressan/CAF09_V1.2S_M36_LT/no_phase/Z0.01.tar.gz"
+        );
+    }
+
+    #[test]
+    fn data_dir_override_is_used() {
+        let dir = std::env::temp_dir().join("astro_utils_data_dir_override");
+        let data_dir = data_dir_with_override(Some(dir.clone().into_os_string())).unwrap();
+        assert_eq!(data_dir, dir);
+    }
+
+    #[test]
+    fn empty_data_dir_override_is_ignored() {
+        let data_dir = data_dir_with_override(Some(OsString::new())).unwrap();
+        assert_eq!(data_dir, data_dir_with_override(None).unwrap());
+    }
+
+    #[test]
+    fn parsec_data_is_read_from_given_directory() {
+        let dir = std::env::temp_dir().join("astro_utils_parsec_data_in_dir");
+        fs::create_dir_all(&dir).unwrap();
+        let stub = ParsecData::test_stub();
+        let buffer = rmp_serde::to_vec(&stub).unwrap();
+        fs::write(dir.join("Z0.01.rmp"), buffer).unwrap();
+
+        let parsec_data = ParsecData::new_in(dir.clone(), "Z0.01").unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let sun_index = ParsecData::get_closest_mass_index(1.);
+        assert!(parsec_data.is_filled());
+        assert_eq!(
+            parsec_data.get_trajectory_via_index(sun_index).lifetime,
+            stub.get_trajectory_via_index(sun_index).lifetime
+        );
+    }
+
+    #[test]
+    fn parsec_data_is_read_from_bytes() {
+        let buffer = rmp_serde::to_vec(&ParsecData::test_stub()).unwrap();
+        let parsec_data = ParsecData::from_bytes(&buffer).unwrap();
+        assert!(parsec_data.is_filled());
+    }
+
+    #[cfg(not(feature = "download"))]
+    #[test]
+    fn offline_construction_succeeds_without_download() {
+        let dir = std::env::temp_dir().join("astro_utils_parsec_data_without_download");
+        fs::create_dir_all(&dir).unwrap();
+        let buffer = rmp_serde::to_vec(&ParsecData::test_stub()).unwrap();
+        fs::write(dir.join("Z0.01.rmp"), buffer).unwrap();
+
+        let parsec_data = ParsecData::new_in(dir.clone(), "Z0.01");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(parsec_data.unwrap().is_filled());
+    }
+
+    #[cfg(not(feature = "download"))]
+    #[test]
+    fn missing_data_is_an_error_without_download() {
+        let dir = std::env::temp_dir().join("astro_utils_parsec_data_offline");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let result = ParsecData::new_in(dir.clone(), "Z0.01");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(AstroUtilError::DataNotAvailable(_))));
+    }
+}
@@ -0,0 +1,146 @@
+use super::data::ParsecData;
+use super::file::{get_mirrors, DOWNLOAD_ATTEMPTS, INITIAL_RETRY_DELAY};
+use crate::error::AstroUtilError;
+use flate2::read::GzDecoder;
+use std::path::{Path, PathBuf};
+use tar::Archive;
+
+impl ParsecData {
+    /*
+     * Makes sure the raw PARSEC files for the given metallicity exist in data_dir without blocking the async runtime.
+     * Afterwards, ParsecData::new_in can read them without network access.
+     */
+    pub async fn ensure_data_files_async(
+        data_dir: &Path,
+        metallicity: &str,
+    ) -> Result<(), AstroUtilError> {
+        let path = data_dir.join(PathBuf::from(metallicity));
+        if !path.exists() {
+            Self::download_async(data_dir, metallicity).await?;
+        }
+        Ok(())
+    }
+
+    async fn download_async(data_dir: &Path, metallicity: &str) -> Result<(), AstroUtilError> {
+        tokio::fs::create_dir_all(data_dir)
+            .await
+            .map_err(AstroUtilError::Io)?;
+        println!("Downloading PARSEC data to {}", data_dir.display());
+        let mut last_error = None;
+        let mut bytes = None;
+        for mirror in get_mirrors() {
+            let target = Self::download_url(&mirror, metallicity);
+            match fetch_with_retries(&target).await {
+                Ok(fetched) => {
+                    bytes = Some(fetched);
+                    break;
+                }
+                Err(err) => last_error = Some(err),
+            }
+        }
+        let bytes = match (bytes, last_error) {
+            (Some(bytes), _) => bytes,
+            (None, Some(err)) => return Err(AstroUtilError::Connection(err)),
+            (None, None) => {
+                return Err(AstroUtilError::DataNotAvailable(
+                    "PARSEC download mirror".to_string(),
+                ))
+            }
+        };
+
+        let data_dir = data_dir.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let gz_decoder = GzDecoder::new(bytes.as_slice());
+            let mut archive = Archive::new(gz_decoder);
+            archive.unpack(data_dir)
+        })
+        .await
+        .map_err(|err| AstroUtilError::Io(std::io::Error::new(std::io::ErrorKind::Other, err)))?
+        .map_err(AstroUtilError::Io)
+    }
+}
+
+async fn fetch_with_retries(target: &str) -> Result<Vec<u8>, reqwest::Error> {
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        let result = match reqwest::get(target).await {
+            Ok(response) => match response.error_for_status() {
+                Ok(response) => response.bytes().await.map(|bytes| bytes.to_vec()),
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(bytes) => return Ok(bytes),
+            Err(err) if attempt >= DOWNLOAD_ATTEMPTS => return Err(err),
+            Err(_) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn list_files(dir: &Path) -> Vec<(String, u64)> {
+        let mut files = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                let name = entry.file_name().to_string_lossy().to_string();
+                (name, entry.metadata().unwrap().len())
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[tokio::test]
+    async fn existing_data_files_are_not_downloaded_again() {
+        let metallicity = "Z0.01";
+        let data_dir = std::env::temp_dir().join("astro_utils_existing_async_data");
+        let _ = fs::remove_dir_all(&data_dir);
+        fs::create_dir_all(data_dir.join(metallicity)).unwrap();
+        fs::write(data_dir.join(metallicity).join("Schnuffelpuff.DAT"), "42").unwrap();
+        let before = list_files(&data_dir.join(metallicity));
+
+        let result = ParsecData::ensure_data_files_async(&data_dir, metallicity).await;
+
+        let after = list_files(&data_dir.join(metallicity));
+        fs::remove_dir_all(&data_dir).unwrap();
+        assert!(result.is_ok());
+        assert_eq!(before, after);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn async_download_matches_blocking_download() {
+        let metallicity = "Z0.01";
+        let blocking_dir = std::env::temp_dir().join("astro_utils_blocking_download");
+        let async_dir = std::env::temp_dir().join("astro_utils_async_download");
+        let _ = fs::remove_dir_all(&blocking_dir);
+        let _ = fs::remove_dir_all(&async_dir);
+
+        let blocking = {
+            let blocking_dir = blocking_dir.clone();
+            tokio::task::spawn_blocking(move || ParsecData::new_in(blocking_dir, metallicity))
+        };
+        blocking.await.unwrap().unwrap();
+        ParsecData::ensure_data_files_async(&async_dir, metallicity)
+            .await
+            .unwrap();
+
+        let blocking_files = list_files(&blocking_dir.join(metallicity));
+        let async_files = list_files(&async_dir.join(metallicity));
+        fs::remove_dir_all(&blocking_dir).unwrap();
+        fs::remove_dir_all(&async_dir).unwrap();
+        assert!(!async_files.is_empty());
+        assert_eq!(blocking_files, async_files);
+    }
+}
@@ -1,9 +1,12 @@
 use astro_coords::cartesian::Cartesian;
-use simple_si_units::base::{Luminosity, Mass, Time};
+use simple_si_units::base::{Luminosity, Mass, Temperature, Time};
+use simple_si_units::electromagnetic::Illuminance;
 
+use crate::error::AstroUtilError;
 use crate::stars::data::StarData;
 use crate::stars::fate::TYPE_II_SUPERNOVA_PEAK_MAGNITUDE;
 use crate::stars::random::random_stars::get_min_age;
+use crate::stars::substellar::substellar_star_data;
 use crate::units::luminous_intensity::{
     absolute_magnitude_to_luminous_intensity, LUMINOSITY_ZERO, SOLAR_LUMINOUS_INTENSITY,
 };
@@ -44,10 +47,40 @@ impl ParsecData {
         }
     }
 
+    /*
+     * The smallest and largest initial mass covered by the PARSEC models.
+     */
+    pub fn mass_range() -> (Mass<f64>, Mass<f64>) {
+        let min = Self::SORTED_MASSES[0];
+        let max = Self::SORTED_MASSES[Self::SORTED_MASSES.len() - 1];
+        (Mass::from_solar_mass(min), Mass::from_solar_mass(max))
+    }
+
+    /*
+     * The index of the PARSEC model closest to the given initial mass.
+     * Masses outside of mass_range are reported instead of being mapped to the edge of the grid.
+     */
+    pub fn try_get_closest_mass_index(mass: Mass<f64>) -> Result<usize, AstroUtilError> {
+        let (min, max) = Self::mass_range();
+        if mass.kg.is_nan() || mass < min || mass > max {
+            return Err(AstroUtilError::DataNotAvailable(format!(
+                "PARSEC model for {:.3} solar masses (the grid covers {} to {})",
+                mass.to_solar_mass(),
+                min.to_solar_mass(),
+                max.to_solar_mass()
+            )));
+        }
+        Ok(Self::get_closest_mass_index(mass.to_solar_mass()))
+    }
+
     pub(super) fn get_trajectory_via_index(&self, i: usize) -> &Trajectory {
         &self.data[i]
     }
 
+    pub(super) fn try_get_trajectory(&self, i: usize) -> Option<&Trajectory> {
+        self.data.get(i)
+    }
+
     pub(super) fn is_filled(&self) -> bool {
         let mut is_filled = !self.data.is_empty();
         for trajectory in self.data.iter() {
@@ -56,24 +89,76 @@ impl ParsecData {
         is_filled
     }
 
+    pub fn is_beyond_lifetime(&self, mass_index: usize, age: Time<f64>) -> bool {
+        match self.data.get(mass_index) {
+            Some(trajectory) => age >= trajectory.lifetime,
+            None => true,
+        }
+    }
+
+    /*
+     * The temperature of the model closest to the given mass and age.
+     * Beyond the lifetime, the temperature of the last model is returned.
+     */
+    pub(crate) fn get_temperature(
+        &self,
+        mass: Mass<f64>,
+        age: Time<f64>,
+    ) -> Option<Temperature<f64>> {
+        let mass_index = Self::try_get_closest_mass_index(mass).ok()?;
+        let trajectory = self.try_get_trajectory(mass_index)?;
+        if trajectory.is_empty() {
+            return None;
+        }
+        let age_index = trajectory.get_closest_params_index(age.to_yr());
+        let params = trajectory.get_params_by_index(age_index)?;
+        Some(Temperature::from_K(params.temperature_in_kelvin))
+    }
+
+    /*
+     * The star of the given initial mass and age at pos.
+     * Its physical parameters are those of the PARSEC model closest in mass and age.
+     * Masses below the PARSEC grid that are heavy enough to be brown dwarfs are handled by the sub-stellar model.
+     */
+    pub fn get_star_data(
+        &self,
+        mass: Mass<f64>,
+        age: Time<f64>,
+        pos: Cartesian,
+    ) -> Result<StarData, AstroUtilError> {
+        let (min_mass, _) = Self::mass_range();
+        if mass < min_mass {
+            return substellar_star_data(mass, age, pos);
+        }
+        let mass_index = Self::try_get_closest_mass_index(mass)?;
+        match self.try_get_trajectory(mass_index) {
+            Some(trajectory) if !trajectory.is_empty() => Ok(trajectory.to_star(age, pos)),
+            _ => Err(AstroUtilError::DataNotAvailable(format!(
+                "PARSEC trajectory for {:.3} solar masses",
+                mass.to_solar_mass()
+            ))),
+        }
+    }
+
     pub(crate) fn get_star_data_if_visible(
         &self,
         mass_index: usize,
         age: Time<f64>,
         pos: Cartesian,
+        dimmest_illuminance: Illuminance<f64>,
     ) -> Option<StarData> {
-        let trajectory = self.get_trajectory_via_index(mass_index);
-        let was_alive_10_millenia_ago = age - TEN_MILLENIA < trajectory.lifetime;
-        if !was_alive_10_millenia_ago {
+        if self.is_beyond_lifetime(mass_index, age - TEN_MILLENIA) {
             return None;
         }
+        let trajectory = self.try_get_trajectory(mass_index)?;
 
         let age_index = trajectory.get_closest_params_index(age.to_yr());
         let params = trajectory.get_params_by_index(age_index)?;
 
-        let is_currently_visible = params.is_visible(&pos);
-        let has_visible_death_within_10k_years =
-            trajectory.is_visible_supernova(&pos) && age + TEN_MILLENIA > trajectory.lifetime;
+        let is_currently_visible = params.is_visible(&pos, dimmest_illuminance);
+        let has_visible_death_within_10k_years = trajectory
+            .is_visible_supernova(&pos, dimmest_illuminance)
+            && age + TEN_MILLENIA > trajectory.lifetime;
         if is_currently_visible || has_visible_death_within_10k_years {
             Some(trajectory.to_star(age, pos))
         } else {
@@ -120,7 +205,8 @@ mod tests {
     use crate::{
         astro_display::AstroDisplay,
         real_data::stars::all::get_many_stars,
-        stars::random::parsec::data::PARSEC_DATA,
+        stars::random::{parsec::data::PARSEC_DATA, random_stars::DIMMEST_ILLUMINANCE},
+        tests::eq,
         units::{luminous_intensity::luminous_intensity_to_illuminance, time::TIME_ZERO},
     };
 
@@ -148,6 +234,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn masses_outside_the_grid_are_reported() {
+        let (min, max) = ParsecData::mass_range();
+        assert!(eq(min.to_solar_mass(), 0.09));
+        assert!(eq(max.to_solar_mass(), 350.));
+        assert!(ParsecData::try_get_closest_mass_index(Mass::from_solar_mass(500.)).is_err());
+        assert!(ParsecData::try_get_closest_mass_index(Mass::from_solar_mass(0.05)).is_err());
+        assert_eq!(
+            ParsecData::try_get_closest_mass_index(max).unwrap(),
+            ParsecData::SORTED_MASSES.len() - 1
+        );
+        let parsec_data = ParsecData::test_stub();
+        assert!(parsec_data
+            .get_temperature(Mass::from_solar_mass(500.), Time::from_Gyr(1.))
+            .is_none());
+    }
+
+    #[test]
+    fn sun_is_beyond_lifetime_only_when_old() {
+        let parsec_data = ParsecData::test_stub();
+        let mass_index = ParsecData::get_closest_mass_index(1.);
+        assert!(!parsec_data.is_beyond_lifetime(mass_index, Time::from_Gyr(1.)));
+        assert!(parsec_data.is_beyond_lifetime(mass_index, Time::from_Gyr(20.)));
+    }
+
+    #[test]
+    fn out_of_range_trajectory_index_returns_none() {
+        let parsec_data = ParsecData::test_stub();
+        let mass_index = ParsecData::SORTED_MASSES.len();
+        assert!(parsec_data.try_get_trajectory(mass_index).is_none());
+        assert!(parsec_data.try_get_trajectory(mass_index - 1).is_some());
+        let star = parsec_data.get_star_data_if_visible(
+            mass_index,
+            TIME_ZERO,
+            Cartesian::ORIGIN,
+            DIMMEST_ILLUMINANCE,
+        );
+        assert!(star.is_none());
+    }
+
+    #[test]
+    fn unknown_mass_index_is_beyond_lifetime() {
+        let parsec_data = ParsecData::test_stub();
+        let mass_index = ParsecData::SORTED_MASSES.len();
+        assert!(parsec_data.is_beyond_lifetime(mass_index, TIME_ZERO));
+    }
+
+    #[test]
+    fn visibility_depends_on_illuminance_cutoff() {
+        let parsec_data = ParsecData::test_stub();
+        let mass_index = ParsecData::get_closest_mass_index(1.);
+        let age = Time::from_Gyr(4.6);
+        let illuminance_at_position = DIMMEST_ILLUMINANCE * 0.5;
+        let distance = Distance {
+            m: (SOLAR_LUMINOUS_INTENSITY.cd / illuminance_at_position.lux).sqrt(),
+        };
+        let pos = Direction::X.to_cartesian(distance);
+
+        let star =
+            parsec_data.get_star_data_if_visible(mass_index, age, pos.clone(), DIMMEST_ILLUMINANCE);
+        assert!(star.is_none());
+
+        let lower_cutoff = DIMMEST_ILLUMINANCE * 0.25;
+        let star = parsec_data.get_star_data_if_visible(mass_index, age, pos, lower_cutoff);
+        assert!(star.is_some());
+    }
+
     #[test]
     fn closest_params_map_to_correct_age() {
         for mass_index in 0..ParsecData::SORTED_MASSES.len() {
@@ -194,7 +347,12 @@ mod tests {
             let parsec_data_mutex = PARSEC_DATA.lock().unwrap();
             let parsec_data = parsec_data_mutex.as_ref().unwrap();
             parsec_data
-                .get_star_data_if_visible(mass_index, TIME_ZERO, Cartesian::ORIGIN)
+                .get_star_data_if_visible(
+                    mass_index,
+                    TIME_ZERO,
+                    Cartesian::ORIGIN,
+                    DIMMEST_ILLUMINANCE,
+                )
                 .unwrap()
         };
         assert!(star
@@ -219,7 +377,12 @@ mod tests {
             let parsec_data = parsec_data_mutex.as_ref().unwrap();
             let trajectory = &parsec_data.get_trajectory_via_index(mass_index);
             let age = trajectory.lifetime;
-            parsec_data.get_star_data_if_visible(mass_index, age, Cartesian::ORIGIN)
+            parsec_data.get_star_data_if_visible(
+                mass_index,
+                age,
+                Cartesian::ORIGIN,
+                DIMMEST_ILLUMINANCE,
+            )
         };
         let star = star.unwrap();
         assert!(star
@@ -245,7 +408,7 @@ mod tests {
             let trajectory = &parsec_data.get_trajectory_via_index(mass_index);
             let age = trajectory.lifetime / 2.;
             parsec_data
-                .get_star_data_if_visible(mass_index, age, Cartesian::ORIGIN)
+                .get_star_data_if_visible(mass_index, age, Cartesian::ORIGIN, DIMMEST_ILLUMINANCE)
                 .unwrap()
         };
         assert!(star.evolution.get_lifestage_mass_per_year().kg < 0.);
@@ -321,7 +484,7 @@ mod tests {
             let generated = {
                 let parsec_data_mutex = PARSEC_DATA.lock().unwrap();
                 let parsec_data = parsec_data_mutex.as_ref().unwrap();
-                parsec_data.get_star_data_if_visible(mass_index, age, pos)
+                parsec_data.get_star_data_if_visible(mass_index, age, pos, DIMMEST_ILLUMINANCE)
             };
             if generated.is_none() {
                 failures += 1;
@@ -347,7 +510,12 @@ mod tests {
             let star = {
                 let parsec_data_mutex = PARSEC_DATA.lock().unwrap();
                 let parsec_data = parsec_data_mutex.as_ref().unwrap();
-                parsec_data.get_star_data_if_visible(mass_index, age, pos.clone())
+                parsec_data.get_star_data_if_visible(
+                    mass_index,
+                    age,
+                    pos.clone(),
+                    DIMMEST_ILLUMINANCE,
+                )
             };
             assert!(
                 star.is_none(),
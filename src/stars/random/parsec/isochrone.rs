@@ -0,0 +1,63 @@
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use simple_si_units::base::{Luminosity, Temperature, Time};
+
+use crate::units::luminous_intensity::SOLAR_LUMINOUS_INTENSITY;
+
+use super::data::ParsecData;
+
+impl ParsecData {
+    /*
+     * For each age, returns the temperature and luminous intensity of all models that are still alive at that age.
+     */
+    pub fn isochrones(&self, ages: &[Time<f64>]) -> Vec<Vec<(Temperature<f64>, Luminosity<f64>)>> {
+        #[cfg(feature = "parallel")]
+        let ages = ages.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let ages = ages.iter();
+        ages.map(|age| self.isochrone(*age)).collect()
+    }
+
+    fn isochrone(&self, age: Time<f64>) -> Vec<(Temperature<f64>, Luminosity<f64>)> {
+        self.data
+            .iter()
+            .filter(|trajectory| !trajectory.is_empty() && age < trajectory.lifetime)
+            .map(|trajectory| {
+                let age_index = trajectory.get_closest_params_index(age.to_yr());
+                let params = trajectory.get_params_by_index_unchecked(age_index);
+                (
+                    Temperature::from_K(params.temperature_in_kelvin),
+                    params.luminous_intensity_in_solar * SOLAR_LUMINOUS_INTENSITY,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn there_is_one_isochrone_per_age() {
+        let parsec_data = ParsecData::test_stub();
+        let ages = [
+            Time::from_yr(1e7),
+            Time::from_yr(1e8),
+            Time::from_Gyr(1.),
+            Time::from_Gyr(4.6),
+        ];
+        let isochrones = parsec_data.isochrones(&ages);
+        assert_eq!(isochrones.len(), ages.len());
+        for isochrone in isochrones.iter() {
+            assert!(!isochrone.is_empty());
+        }
+    }
+
+    #[test]
+    fn massive_stars_drop_out_of_older_isochrones() {
+        let parsec_data = ParsecData::test_stub();
+        let isochrones = parsec_data.isochrones(&[Time::from_yr(1e7), Time::from_Gyr(4.6)]);
+        assert!(isochrones[0].len() > isochrones[1].len());
+    }
+}
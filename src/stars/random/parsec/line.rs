@@ -1,12 +1,18 @@
 use super::data::ParsecData;
 use crate::{
     error::AstroUtilError,
-    stars::random::random_stars::DIMMEST_ILLUMINANCE,
-    units::{distance::distance_to_sun_radii, luminous_intensity::SOLAR_LUMINOUS_INTENSITY},
+    stars::physical_parameters::StarPhysicalParameters,
+    units::{
+        distance::{distance_to_sun_radii, SOLAR_RADIUS},
+        luminous_intensity::SOLAR_LUMINOUS_INTENSITY,
+    },
 };
 use astro_coords::cartesian::Cartesian;
 use serde::{Deserialize, Serialize};
-use simple_si_units::base::{Distance, Luminosity};
+use simple_si_units::{
+    base::{Distance, Luminosity, Mass, Temperature},
+    electromagnetic::Illuminance,
+};
 
 pub(super) struct ParsecLine {
     mass: f64,
@@ -101,10 +107,64 @@ impl ParsecLine {
 }
 
 impl ParsedParsecLine {
-    pub(super) fn is_visible(&self, pos: &Cartesian) -> bool {
+    pub(super) fn is_visible(
+        &self,
+        pos: &Cartesian,
+        dimmest_illuminance: Illuminance<f64>,
+    ) -> bool {
         let min_luminous_intensity = Luminosity {
-            cd: DIMMEST_ILLUMINANCE.lux * pos.length_squared().m2,
+            cd: dimmest_illuminance.lux * pos.length_squared().m2,
         };
         self.luminous_intensity_in_solar * SOLAR_LUMINOUS_INTENSITY >= min_luminous_intensity
     }
 }
+
+impl From<&ParsedParsecLine> for StarPhysicalParameters {
+    fn from(line: &ParsedParsecLine) -> Self {
+        StarPhysicalParameters::new(
+            Some(Mass::from_solar_mass(line.mass_in_solar_masses)),
+            Some(line.radius_in_solar_radii * SOLAR_RADIUS),
+            line.luminous_intensity_in_solar * SOLAR_LUMINOUS_INTENSITY,
+            Temperature::from_K(line.temperature_in_kelvin),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::eq;
+    use simple_si_units::base::Time;
+
+    #[test]
+    fn parsec_star_converts_to_star_data() {
+        let parsec_data = ParsecData::test_stub();
+        let mass = Mass::from_solar_mass(1.2);
+        let age = Time::from_Gyr(2.);
+        let star = parsec_data
+            .get_star_data(mass, age, Cartesian::ORIGIN)
+            .unwrap();
+
+        let mass_index = ParsecData::try_get_closest_mass_index(mass).unwrap();
+        let trajectory = parsec_data.get_trajectory_via_index(mass_index);
+        let age_index = trajectory.get_closest_params_index(age.to_yr());
+        let line = trajectory.get_params_by_index(age_index).unwrap();
+
+        assert!(eq(
+            star.get_mass_at_epoch().unwrap().to_solar_mass(),
+            line.mass_in_solar_masses
+        ));
+        assert!(eq(
+            star.get_temperature_at_epoch().to_K(),
+            line.temperature_in_kelvin
+        ));
+        assert!(eq(
+            distance_to_sun_radii(&star.get_radius_at_epoch().unwrap()),
+            line.radius_in_solar_radii
+        ));
+        assert!(eq(
+            star.get_luminous_intensity_at_epoch() / SOLAR_LUMINOUS_INTENSITY,
+            line.luminous_intensity_in_solar
+        ));
+    }
+}
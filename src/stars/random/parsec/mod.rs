@@ -1,6 +1,10 @@
-pub(super) mod data;
+pub mod data;
 pub(super) mod distributions;
+mod estimation;
 mod file;
+#[cfg(feature = "async")]
+mod file_async;
 mod getters;
+mod isochrone;
 mod line;
 mod trajectory;
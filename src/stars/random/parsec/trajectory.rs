@@ -1,6 +1,9 @@
 use astro_coords::cartesian::Cartesian;
 use serde::{Deserialize, Serialize};
-use simple_si_units::base::{Luminosity, Mass, Temperature, Time};
+use simple_si_units::{
+    base::{Luminosity, Mass, Time},
+    electromagnetic::Illuminance,
+};
 
 use crate::{
     stars::{
@@ -8,10 +11,8 @@ use crate::{
         evolution::{StarDataEvolution, StarDataLifestageEvolution},
         fate::{StarFate, TYPE_II_SUPERNOVA_PEAK_MAGNITUDE},
         physical_parameters::StarPhysicalParameters,
-        random::random_stars::DIMMEST_ILLUMINANCE,
     },
     units::{
-        distance::SOLAR_RADIUS,
         luminous_intensity::{
             absolute_magnitude_to_luminous_intensity, LUMINOSITY_ZERO, SOLAR_LUMINOUS_INTENSITY,
         },
@@ -96,7 +97,6 @@ impl Trajectory {
         }
     }
 
-    #[cfg(test)]
     pub(super) fn get_params(&self) -> &Vec<ParsedParsecLine> {
         &self.params
     }
@@ -105,12 +105,16 @@ impl Trajectory {
         self.params.is_empty()
     }
 
-    pub(super) fn is_visible_supernova(&self, pos: &Cartesian) -> bool {
+    pub(super) fn is_visible_supernova(
+        &self,
+        pos: &Cartesian,
+        dimmest_illuminance: Illuminance<f64>,
+    ) -> bool {
         if self.initial_mass < Mass::from_solar_mass(8.) {
             return false;
         }
         let min_luminous_intensity = Luminosity {
-            cd: DIMMEST_ILLUMINANCE.lux * pos.length_squared().m2,
+            cd: dimmest_illuminance.lux * pos.length_squared().m2,
         };
         self.peak_lifetime_luminous_intensity >= min_luminous_intensity
     }
@@ -134,16 +138,7 @@ impl Trajectory {
 
     fn to_star_without_evolution(&self, age_index: usize, pos: Cartesian) -> StarData {
         let params = self.get_params_by_index_unchecked(age_index);
-        let mass = Mass::from_solar_mass(params.mass_in_solar_masses);
-        let luminous_intensity = params.luminous_intensity_in_solar * SOLAR_LUMINOUS_INTENSITY;
-        let temperature = Temperature::from_K(params.temperature_in_kelvin);
-        let radius = params.radius_in_solar_radii * SOLAR_RADIUS;
-        let physical_parameters = StarPhysicalParameters {
-            mass: Some(mass),
-            luminous_intensity,
-            temperature,
-            radius: Some(radius),
-        };
+        let physical_parameters = StarPhysicalParameters::from(params);
         let mut evolution = StarDataEvolution::NONE;
         evolution.age = Some(Time::from_yr(params.age_in_years));
         StarData {
@@ -152,6 +147,7 @@ impl Trajectory {
             pos,
             constellation: None,
             evolution,
+            proper_motion: None,
         }
     }
 }
@@ -1,21 +1,28 @@
-use super::{params::GenerationParams, parsec::data::ParsecData};
+use super::{extinction::GalacticExtinction, params::GenerationParams, parsec::data::ParsecData};
 use crate::{
     error::AstroUtilError,
     stars::{
         data::StarData,
         random::parsec::{data::PARSEC_DATA, distributions::ParsecDistribution},
     },
-    units::time::TEN_MILLENIA,
+    units::{distance::DISTANCE_ZERO, illuminance::scaled_illuminance, time::TEN_MILLENIA},
 };
 use astro_coords::{cartesian::Cartesian, direction::Direction};
-use rand::{distributions::Uniform, rngs::ThreadRng, Rng};
+use rand::{distributions::Uniform, rngs::StdRng, Rng, SeedableRng};
+#[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use simple_si_units::{
     base::{Distance, Time},
     electromagnetic::Illuminance,
     mechanical::Velocity,
 };
-use std::f64::consts::PI;
+use std::{
+    f64::consts::PI,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 // https://en.wikipedia.org/wiki/Stellar_density
 // But more or less arbitrarily adjusted to reproduce Gaia data.
@@ -31,42 +38,309 @@ pub(super) const AGE_OF_MILKY_WAY_THIN_DISK: Time<f64> = Time {
 const NURSERIES_PER_LY_CUBED: f64 = 6_000. / 8e12 * 10.; //* AGE_OF_MILKY_WAY_THIN_DISK.s / NURSERY_LIFETIME.s;
 pub(super) const NUMBER_OF_STARS_FORMED_IN_NURSERY: usize = 20_000;
 pub(super) const STELLAR_VELOCITY: Velocity<f64> = Velocity { mps: 20_000. };
-pub(super) const DIMMEST_ILLUMINANCE: Illuminance<f64> = Illuminance { lux: 6.5309e-9 };
+pub const DIMMEST_ILLUMINANCE: Illuminance<f64> = Illuminance { lux: 6.5309e-9 };
 
-pub fn generate_random_stars(max_distance: Distance<f64>) -> Result<Vec<StarData>, AstroUtilError> {
-    let parsec_data_mutex = PARSEC_DATA
-        .lock()
-        .map_err(|_| AstroUtilError::MutexPoison)?;
-    let parsec_data = parsec_data_mutex.as_ref()?;
+/*
+ * Collects the settings for generating random stars.
+ * Stars are generated within a spherical shell between min_distance, which defaults to zero, and max_distance.
+ * Without a seed, every generation yields a different population.
+ * Without an extinction model, the space between the stars is perfectly transparent.
+ * With one, only stars that remain visible through the dust are generated, but their data stays intrinsic.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationConfig {
+    min_distance: Distance<f64>,
+    max_distance: Distance<f64>,
+    seed: Option<u64>,
+    extinction: Option<GalacticExtinction>,
+}
+
+impl GenerationConfig {
+    pub fn new(max_distance: Distance<f64>) -> Self {
+        Self {
+            min_distance: DISTANCE_ZERO,
+            max_distance,
+            seed: None,
+            extinction: None,
+        }
+    }
+
+    pub fn with_min_distance(mut self, min_distance: Distance<f64>) -> Self {
+        self.min_distance = min_distance;
+        self
+    }
+
+    pub fn with_max_distance(mut self, max_distance: Distance<f64>) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_extinction(mut self, extinction: GalacticExtinction) -> Self {
+        self.extinction = Some(extinction);
+        self
+    }
+
+    pub fn get_min_distance(&self) -> Distance<f64> {
+        self.min_distance
+    }
+
+    pub fn get_max_distance(&self) -> Distance<f64> {
+        self.max_distance
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn get_extinction(&self) -> Option<GalacticExtinction> {
+        self.extinction
+    }
+}
+
+pub fn generate(config: &GenerationConfig) -> Result<Vec<StarData>, AstroUtilError> {
+    generate_cancellable(config, &AtomicBool::new(false))
+}
+
+/*
+ * Once the cancellation flag is set, no further stars are generated.
+ * The stars that have been generated up to that point are returned.
+ */
+pub fn generate_cancellable(
+    config: &GenerationConfig,
+    cancel: &AtomicBool,
+) -> Result<Vec<StarData>, AstroUtilError> {
+    let parsec_data = get_parsec_data()?;
+    generate_cancellable_from_data(config, &parsec_data, cancel)
+}
+
+/*
+ * Generates the same stars as generate, but from PARSEC data that has already been loaded, e.g. via ParsecData::from_bytes.
+ * The data directory is not needed, which makes it usable on wasm targets, where the parallel feature should be disabled.
+ */
+pub fn generate_from_data(
+    config: &GenerationConfig,
+    parsec_data: &ParsecData,
+) -> Result<Vec<StarData>, AstroUtilError> {
+    generate_cancellable_from_data(config, parsec_data, &AtomicBool::new(false))
+}
+
+fn generate_cancellable_from_data(
+    config: &GenerationConfig,
+    parsec_data: &ParsecData,
+    cancel: &AtomicBool,
+) -> Result<Vec<StarData>, AstroUtilError> {
     let parsec_distr = ParsecDistribution::new()?;
+    let seed = config.seed.unwrap_or_else(rand::random);
 
-    let number_star_forming_regions = number_in_sphere(NURSERIES_PER_LY_CUBED, max_distance) + 1;
-    let age_distribution = Uniform::new(0., AGE_OF_MILKY_WAY_THIN_DISK.s);
+    let number_star_forming_regions = number_of_star_forming_regions(config.max_distance);
     println!(
         "Number of star forming regions: {}",
         number_star_forming_regions
     );
-    let stars = (0..number_star_forming_regions)
-        .into_par_iter()
-        .map(|i| {
-            let mut rng = rand::thread_rng();
-            let mut params = if i == 0 {
-                GenerationParams::old_stars(max_distance)
-            } else {
-                let pos = random_point_in_sphere(&mut rng, max_distance);
-                let max_age = Time {
-                    s: rng.sample(age_distribution),
-                };
-                GenerationParams::nursery(pos, max_age)
-            };
-            params.adjust_distance_for_performance(parsec_data);
-            generate_random_stars_with_params(params, parsec_data, &parsec_distr)
+    #[cfg(feature = "parallel")]
+    let regions = (0..number_star_forming_regions).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let regions = 0..number_star_forming_regions;
+    let stars = regions
+        .flat_map(|i| {
+            if is_cancelled(cancel) {
+                return vec![];
+            }
+            let region_seed = sub_seed(seed, i as u64);
+            let mut rng = StdRng::seed_from_u64(region_seed);
+            let params = generation_params(i, config, parsec_data, &mut rng);
+            generate_random_stars_with_params(
+                params,
+                region_seed,
+                parsec_data,
+                &parsec_distr,
+                config.extinction.as_ref(),
+                cancel,
+            )
         })
-        .flatten()
         .collect();
     Ok(stars)
 }
 
+pub fn generate_random_stars(max_distance: Distance<f64>) -> Result<Vec<StarData>, AstroUtilError> {
+    generate(&GenerationConfig::new(max_distance))
+}
+
+pub fn generate_random_stars_cancellable(
+    max_distance: Distance<f64>,
+    cancel: &AtomicBool,
+) -> Result<Vec<StarData>, AstroUtilError> {
+    generate_cancellable(&GenerationConfig::new(max_distance), cancel)
+}
+
+/*
+ * Regenerates the star with the given index out of all candidates that generate would consider, without generating the others.
+ * None is returned if that candidate is not visible, or if the index lies beyond the last candidate.
+ * The stars are only reproducible if the config has a seed, in which case generate returns exactly the visible ones in order.
+ */
+pub fn generate_star_by_index(config: &GenerationConfig, index: usize) -> Option<StarData> {
+    let parsec_data = get_parsec_data().ok()?;
+    let parsec_distr = ParsecDistribution::new().ok()?;
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut remaining = index;
+    for i in 0..number_of_star_forming_regions(config.max_distance) {
+        let region_seed = sub_seed(seed, i as u64);
+        let mut rng = StdRng::seed_from_u64(region_seed);
+        let params = generation_params(i, config, &parsec_data, &mut rng);
+        let candidates = params.number + 1;
+        if remaining < candidates {
+            let mut rng = StdRng::seed_from_u64(sub_seed(region_seed, remaining as u64));
+            return generate_visible_random_star_with_params(
+                &params,
+                &parsec_data,
+                &parsec_distr,
+                config.extinction.as_ref(),
+                &mut rng,
+            );
+        }
+        remaining -= candidates;
+    }
+    None
+}
+
+/*
+ * Derives independent seeds for the parts of a generation from a single seed, using the SplitMix64 mixing function:
+ * https://prng.di.unimi.it/splitmix64.c
+ */
+fn sub_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/*
+ * Lazily yields the same stars as generate with the given seed, one star at a time and in the same order.
+ * The generation is single-threaded.
+ */
+pub fn random_star_iter(
+    max_distance: Distance<f64>,
+    seed: u64,
+) -> Result<impl Iterator<Item = StarData>, AstroUtilError> {
+    let parsec_data = get_parsec_data()?;
+    let parsec_distr = ParsecDistribution::new()?;
+    Ok(RandomStarIterator {
+        parsec_data,
+        parsec_distr,
+        config: GenerationConfig::new(max_distance),
+        seed,
+        number_of_regions: number_of_star_forming_regions(max_distance),
+        region_index: 0,
+        region: None,
+        star_index: 0,
+    })
+}
+
+/*
+ * Shorthand for generate_from_data with a seeded default config.
+ */
+pub fn generate_random_stars_from_data(
+    max_distance: Distance<f64>,
+    parsec_data: &ParsecData,
+    seed: u64,
+) -> Result<Vec<StarData>, AstroUtilError> {
+    generate_from_data(
+        &GenerationConfig::new(max_distance).with_seed(seed),
+        parsec_data,
+    )
+}
+
+struct RandomStarIterator {
+    parsec_data: Arc<ParsecData>,
+    parsec_distr: ParsecDistribution,
+    config: GenerationConfig,
+    seed: u64,
+    number_of_regions: usize,
+    region_index: usize,
+    region: Option<(u64, GenerationParams)>,
+    star_index: usize,
+}
+
+impl Iterator for RandomStarIterator {
+    type Item = StarData;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.region.is_none() {
+                if self.region_index >= self.number_of_regions {
+                    return None;
+                }
+                let region_seed = sub_seed(self.seed, self.region_index as u64);
+                let mut rng = StdRng::seed_from_u64(region_seed);
+                let params =
+                    generation_params(self.region_index, &self.config, &self.parsec_data, &mut rng);
+                self.region = Some((region_seed, params));
+                self.star_index = 0;
+            }
+            let (region_seed, params) = self.region.as_ref()?;
+            if self.star_index > params.number {
+                self.region = None;
+                self.region_index += 1;
+                continue;
+            }
+            let mut rng = StdRng::seed_from_u64(sub_seed(*region_seed, self.star_index as u64));
+            self.star_index += 1;
+            let star = generate_visible_random_star_with_params(
+                params,
+                &self.parsec_data,
+                &self.parsec_distr,
+                None,
+                &mut rng,
+            );
+            if star.is_some() {
+                return star;
+            }
+        }
+    }
+}
+
+fn get_parsec_data() -> Result<Arc<ParsecData>, AstroUtilError> {
+    let parsec_data_mutex = PARSEC_DATA
+        .lock()
+        .map_err(|_| AstroUtilError::MutexPoison)?;
+    let parsec_data = parsec_data_mutex.as_ref()?;
+    Ok(parsec_data.clone())
+}
+
+fn number_of_star_forming_regions(max_distance: Distance<f64>) -> usize {
+    number_in_sphere(NURSERIES_PER_LY_CUBED, max_distance) + 1
+}
+
+fn generation_params<R: Rng>(
+    index: usize,
+    config: &GenerationConfig,
+    parsec_data: &ParsecData,
+    rng: &mut R,
+) -> GenerationParams {
+    let mut params = if index == 0 {
+        GenerationParams::old_stars(config.max_distance)
+    } else {
+        let age_distribution = Uniform::new(0., AGE_OF_MILKY_WAY_THIN_DISK.s);
+        let pos = random_point_in_sphere(rng, config.max_distance);
+        let max_age = Time {
+            s: rng.sample(age_distribution),
+        };
+        GenerationParams::nursery(pos, max_age)
+    };
+    params.adjust_distance_for_performance(parsec_data);
+    params.exclude_stars_closer_than(config.min_distance);
+    params
+}
+
+fn is_cancelled(cancel: &AtomicBool) -> bool {
+    cancel.load(Ordering::Relaxed)
+}
+
 pub(crate) fn get_min_age(max_age: Time<f64>) -> Time<f64> {
     max_age - NURSERY_LIFETIME - TEN_MILLENIA
 }
@@ -75,44 +349,66 @@ pub(super) fn number_in_sphere(num_per_lyr: f64, max_distance: Distance<f64>) ->
     (num_per_lyr * 4. / 3. * PI * max_distance.to_lyr().powi(3)) as usize
 }
 
+/*
+ * Each star gets its own seed, derived from the seed of its region.
+ */
 fn generate_random_stars_with_params(
     params: GenerationParams,
+    region_seed: u64,
     parsec_data: &ParsecData,
     parsec_distr: &ParsecDistribution,
+    extinction: Option<&GalacticExtinction>,
+    cancel: &AtomicBool,
 ) -> Vec<StarData> {
-    let age_distribution = Uniform::new(0., NURSERY_LIFETIME.s);
     (0..=params.number)
-        .filter_map(|_| {
-            let mut rng = rand::thread_rng();
-            let age = params.max_age
-                - Time {
-                    s: rng.sample(age_distribution),
-                };
-            generate_visible_random_star(
+        .take_while(|_| !is_cancelled(cancel))
+        .filter_map(|j| {
+            let mut rng = StdRng::seed_from_u64(sub_seed(region_seed, j as u64));
+            generate_visible_random_star_with_params(
+                &params,
                 parsec_data,
-                &params.pos,
-                params.radius,
-                age,
-                &mut rng,
                 parsec_distr,
+                extinction,
+                &mut rng,
             )
         })
         .collect::<Vec<StarData>>()
 }
 
+fn generate_visible_random_star_with_params<R: Rng>(
+    params: &GenerationParams,
+    parsec_data: &ParsecData,
+    parsec_distr: &ParsecDistribution,
+    extinction: Option<&GalacticExtinction>,
+    rng: &mut R,
+) -> Option<StarData> {
+    let age_distribution = Uniform::new(0., NURSERY_LIFETIME.s);
+    let age = params.max_age
+        - Time {
+            s: rng.sample(age_distribution),
+        };
+    generate_visible_random_star(
+        parsec_data,
+        &params.pos,
+        params.min_distance,
+        params.radius,
+        age,
+        rng,
+        parsec_distr,
+        extinction,
+    )
+}
+
 pub fn generate_random_star(
     max_distance: Option<Distance<f64>>,
 ) -> Result<StarData, AstroUtilError> {
     let max_distance_or_1 = max_distance.unwrap_or(Distance { m: 1. });
 
-    let parsec_data_mutex = PARSEC_DATA
-        .lock()
-        .map_err(|_| AstroUtilError::MutexPoison)?;
-    let parsec_data = parsec_data_mutex.as_ref()?;
+    let parsec_data = get_parsec_data()?;
     let parsec_distr = ParsecDistribution::new()?;
 
     let mut star =
-        definetely_generate_visible_random_star(parsec_data, max_distance_or_1, parsec_distr);
+        definetely_generate_visible_random_star(&parsec_data, max_distance_or_1, parsec_distr);
     if max_distance.is_none() {
         star.pos = Cartesian::ORIGIN;
     }
@@ -132,10 +428,12 @@ fn definetely_generate_visible_random_star(
                 star = generate_visible_random_star(
                     parsec_data,
                     &Cartesian::ORIGIN,
+                    DISTANCE_ZERO,
                     max_distance_or_1,
                     AGE_OF_MILKY_WAY_THIN_DISK,
                     &mut rng,
                     &parsec_distr,
+                    None,
                 );
             }
             Some(star) => return star,
@@ -143,21 +441,31 @@ fn definetely_generate_visible_random_star(
     }
 }
 
-fn generate_visible_random_star(
+/*
+ * The extinction only raises the threshold for visibility, the returned star keeps its intrinsic luminous intensity.
+ */
+fn generate_visible_random_star<R: Rng>(
     parsec_data: &ParsecData,
     origin: &Cartesian,
+    min_distance: Distance<f64>,
     max_distance: Distance<f64>,
     age: Time<f64>,
-    rng: &mut ThreadRng,
+    rng: &mut R,
     parsec_distr: &ParsecDistribution,
+    extinction: Option<&GalacticExtinction>,
 ) -> Option<StarData> {
     let mass_index = parsec_distr.get_random_mass_index(rng);
-    let pos = origin + &random_point_in_sphere(rng, max_distance);
-    let star = parsec_data.get_star_data_if_visible(mass_index, age, pos)?;
-    Some(star)
+    let inner = excluded_radius(origin, min_distance);
+    let pos = origin + &random_point_in_shell(rng, inner, max_distance);
+    if pos.length() < min_distance {
+        return None;
+    }
+    let transmission = extinction.map_or(1., |extinction| extinction.transmission(&pos));
+    let dimmest_illuminance = scaled_illuminance(&DIMMEST_ILLUMINANCE, 1. / transmission);
+    parsec_data.get_star_data_if_visible(mass_index, age, pos, dimmest_illuminance)
 }
 
-fn random_point_in_unit_sphere(rng: &mut ThreadRng) -> Cartesian {
+fn random_point_in_unit_sphere<R: Rng>(rng: &mut R) -> Cartesian {
     let distr = Uniform::new(-1., 1.);
     let (mut x, mut y, mut z) = (rng.sample(distr), rng.sample(distr), rng.sample(distr));
     while x * x + y * y + z * z > 1. {
@@ -169,12 +477,40 @@ fn random_point_in_unit_sphere(rng: &mut ThreadRng) -> Cartesian {
     Cartesian::new(x, y, z)
 }
 
-fn random_point_in_sphere(rng: &mut ThreadRng, max_distance: Distance<f64>) -> Cartesian {
+fn random_point_in_sphere<R: Rng>(rng: &mut R, max_distance: Distance<f64>) -> Cartesian {
     let point = random_point_in_unit_sphere(rng);
     point * max_distance.m
 }
 
-pub(crate) fn random_direction(rng: &mut ThreadRng) -> Direction {
+/*
+ * Samples uniformly within the volume between the two radii.
+ * The cube of the distance is uniformly distributed between the cubes of the radii.
+ * Without an inner radius, the point is drawn exactly like in random_point_in_sphere.
+ */
+fn random_point_in_shell<R: Rng>(
+    rng: &mut R,
+    min_distance: Distance<f64>,
+    max_distance: Distance<f64>,
+) -> Cartesian {
+    if min_distance.m <= 0. || min_distance >= max_distance {
+        return random_point_in_sphere(rng, max_distance);
+    }
+    let dir = random_direction(rng);
+    let min_cubed = (min_distance / max_distance).powi(3);
+    let cubed = rng.sample(Uniform::new_inclusive(min_cubed, 1.));
+    dir.to_cartesian(cubed.cbrt() * max_distance)
+}
+
+/*
+ * The radius of the sphere around center that lies entirely within min_distance of the origin.
+ */
+pub(super) fn excluded_radius(center: &Cartesian, min_distance: Distance<f64>) -> Distance<f64> {
+    Distance {
+        m: (min_distance - center.length()).m.max(0.),
+    }
+}
+
+pub(crate) fn random_direction<R: Rng>(rng: &mut R) -> Direction {
     let mut point = random_point_in_unit_sphere(rng);
     let mut dir = point.to_direction();
     loop {
@@ -194,13 +530,14 @@ mod tests {
 
     use crate::{
         astro_display::AstroDisplay,
-        stars::fate::StarFate,
+        coordinates::galactic::galactic_latitude,
+        stars::{data::StarId, fate::StarFate},
         tests::eq,
         units::{illuminance::illuminance_to_apparent_magnitude, time::TIME_ZERO},
     };
 
     use super::*;
-    use std::time::Instant;
+    use std::{collections::HashMap, time::Instant};
 
     #[test]
     fn dimmest_illuminance_is_magnitude_6_5() {
@@ -231,6 +568,199 @@ mod tests {
         assert!(duration.as_secs() < max_seconds);
     }
 
+    #[test]
+    fn cancelled_generation_returns_fewer_stars() {
+        let max_distance = Distance::from_lyr(500.);
+        let all_stars = generate_random_stars(max_distance).unwrap();
+        let cancel = AtomicBool::new(true);
+        let cancelled_stars = generate_random_stars_cancellable(max_distance, &cancel).unwrap();
+        println!(
+            "Generated {} stars, and {} stars after cancellation",
+            all_stars.len(),
+            cancelled_stars.len()
+        );
+        assert!(cancelled_stars.len() * 10 < all_stars.len());
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible() {
+        let config = GenerationConfig::new(Distance::from_lyr(500.)).with_seed(42);
+        let first = generate(&config).unwrap();
+        let second = generate(&config).unwrap();
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn seeded_default_config_matches_simple_generation() {
+        let max_distance = Distance::from_lyr(500.);
+        let parsec_data = get_parsec_data().unwrap();
+        let simple = generate_random_stars_from_data(max_distance, &parsec_data, 42).unwrap();
+        let configured = generate(&GenerationConfig::new(max_distance).with_seed(42)).unwrap();
+        assert!(!configured.is_empty());
+        assert_eq!(configured, simple);
+        for star in configured {
+            assert!(star.get_distance_at_epoch() < max_distance * 1.01);
+        }
+    }
+
+    #[test]
+    fn star_by_index_is_reproducible_and_matches_generation() {
+        let config = GenerationConfig::new(Distance::from_lyr(100.)).with_seed(7);
+        let stars = generate(&config).unwrap();
+        let first_index = (0..1_000_000)
+            .find(|i| generate_star_by_index(&config, *i).is_some())
+            .unwrap();
+
+        let star = generate_star_by_index(&config, first_index);
+        assert_eq!(star, generate_star_by_index(&config, first_index));
+        assert_eq!(star.as_ref(), stars.first());
+        assert_ne!(star, generate_star_by_index(&config, first_index + 1));
+    }
+
+    #[test]
+    fn dust_dims_stars_near_the_galactic_plane() {
+        let config = GenerationConfig::new(Distance::from_lyr(500.)).with_seed(42);
+        let transparent: HashMap<StarId, StarData> = generate(&config)
+            .unwrap()
+            .into_iter()
+            .map(|star| (star.id(), star))
+            .collect();
+        let extinction = GalacticExtinction::new(5., Distance::from_parsec(30.));
+        let dusty = generate(&config.with_extinction(extinction)).unwrap();
+        assert!(!dusty.is_empty());
+        assert!(dusty.len() < transparent.len());
+
+        let (mut near_plane, mut high_latitude) = (vec![], vec![]);
+        for star in dusty {
+            assert_eq!(star, transparent[&star.id()]);
+            let dimming = star.to_star_appearance(TIME_ZERO).get_illuminance().lux
+                / extinction
+                    .to_star_appearance(&star, TIME_ZERO)
+                    .get_illuminance()
+                    .lux;
+            let dir = star.get_pos_at_epoch().to_direction().unwrap();
+            let latitude = galactic_latitude(&dir).to_degrees().abs();
+            if latitude < 10. {
+                near_plane.push(dimming);
+            } else if latitude > 60. {
+                high_latitude.push(dimming);
+            }
+        }
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+        println!(
+            "Mean dimming of {} stars near the plane: {}, of {} stars at high latitude: {}",
+            near_plane.len(),
+            mean(&near_plane),
+            high_latitude.len(),
+            mean(&high_latitude)
+        );
+        assert!(mean(&near_plane) > mean(&high_latitude));
+    }
+
+    #[test]
+    fn stars_are_generated_within_the_shell() {
+        let min_distance = Distance::from_lyr(200.);
+        let max_distance = Distance::from_lyr(500.);
+        let config = GenerationConfig::new(max_distance)
+            .with_min_distance(min_distance)
+            .with_seed(42);
+        let stars = generate(&config).unwrap();
+        assert!(!stars.is_empty());
+        for star in stars {
+            assert!(star.get_distance_at_epoch() >= min_distance);
+            assert!(star.get_distance_at_epoch() < max_distance * 1.01);
+        }
+    }
+
+    #[test]
+    fn sub_seeds_differ() {
+        assert_ne!(sub_seed(42, 0), sub_seed(42, 1));
+        assert_ne!(sub_seed(42, 0), sub_seed(43, 0));
+    }
+
+    #[test]
+    fn random_star_iterator_matches_generation() {
+        let max_distance = Distance::from_lyr(300.);
+        let iterated: Vec<StarData> = random_star_iter(max_distance, 42).unwrap().collect();
+        let generated = generate(&GenerationConfig::new(max_distance).with_seed(42)).unwrap();
+        assert!(!generated.is_empty());
+        assert_eq!(iterated, generated);
+    }
+
+    #[test]
+    fn random_star_iterator_yields_stars_within_max_distance() {
+        let max_distance = Distance::from_lyr(1000.);
+        let stars: Vec<StarData> = random_star_iter(max_distance, 42)
+            .unwrap()
+            .take(100)
+            .collect();
+        assert_eq!(stars.len(), 100);
+        for star in stars {
+            assert!(star.get_distance_at_epoch() < max_distance * 1.01);
+        }
+    }
+
+    #[test]
+    fn random_star_iterator_is_reproducible() {
+        let max_distance = Distance::from_lyr(1000.);
+        let first: Vec<StarData> = random_star_iter(max_distance, 42)
+            .unwrap()
+            .take(10)
+            .collect();
+        let second: Vec<StarData> = random_star_iter(max_distance, 42)
+            .unwrap()
+            .take(10)
+            .collect();
+        assert_eq!(first, second);
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    #[test]
+    fn sequential_generation_returns_stars() {
+        let max_distance = Distance::from_lyr(500.);
+        let stars = generate_random_stars(max_distance).unwrap();
+        assert!(!stars.is_empty());
+    }
+
+    #[test]
+    fn generation_from_data_produces_valid_stars() {
+        let max_distance = Distance::from_lyr(500.);
+        let parsec_data = ParsecData::test_stub();
+        let stars = generate_random_stars_from_data(max_distance, &parsec_data, 42).unwrap();
+        assert!(!stars.is_empty());
+        for star in stars.iter() {
+            assert!(star.validate().is_ok());
+            assert!(star.get_distance_at_epoch() < max_distance * 1.01);
+        }
+        let again = generate_random_stars_from_data(max_distance, &parsec_data, 42).unwrap();
+        assert_eq!(stars, again);
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    #[test]
+    fn sequential_generation_from_data_produces_valid_stars() {
+        let max_distance = Distance::from_lyr(500.);
+        let parsec_data = ParsecData::test_stub();
+        let stars = generate_random_stars_from_data(max_distance, &parsec_data, 42).unwrap();
+        assert!(!stars.is_empty());
+        for star in stars.iter() {
+            assert!(star.validate().is_ok());
+        }
+        let again = generate_random_stars_from_data(max_distance, &parsec_data, 42).unwrap();
+        assert_eq!(stars, again);
+    }
+
+    #[test]
+    fn generation_from_loaded_data_matches_generation() {
+        let max_distance = Distance::from_lyr(300.);
+        let parsec_data = get_parsec_data().unwrap();
+        let from_data = generate_random_stars_from_data(max_distance, &parsec_data, 42).unwrap();
+        let generated = generate(&GenerationConfig::new(max_distance).with_seed(42)).unwrap();
+        assert!(!generated.is_empty());
+        assert_eq!(from_data, generated);
+    }
+
     #[test]
     fn generating_a_distant_random_star() {
         let max_distance = Distance::from_lyr(1000.);
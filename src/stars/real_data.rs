@@ -76,6 +76,7 @@ impl RealData {
             params: physical_parameters,
             pos,
             evolution,
+            proper_motion: None,
         }
     }
 
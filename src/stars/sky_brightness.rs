@@ -0,0 +1,101 @@
+use simple_si_units::{electromagnetic::Illuminance, geometry::Angle};
+
+/*
+ * Illuminance of a clear, moonless night sky due to airglow and unresolved stars.
+ */
+pub const NIGHT_SKY_ILLUMINANCE: Illuminance<f64> = Illuminance { lux: 2e-3 };
+
+/*
+ * Rough illuminance of the sky background (excluding direct sunlight) depending on the altitude of the sun.
+ * Values are interpolated logarithmically between typical values for daylight, sunset,
+ * the end of civil (-6°), nautical (-12°) and astronomical (-18°) twilight.
+ * Below -18° the sky is as dark as it gets.
+ */
+const SUN_ALTITUDE_DEGREES_AND_LOG_LUX: [(f64, f64); 7] = [
+    (-18., -2.7),
+    (-12., -2.),
+    (-6., 0.5),
+    (0., 2.6),
+    (10., 3.7),
+    (30., 4.1),
+    (90., 4.3),
+];
+
+pub fn sky_background_illuminance(sun_altitude: Angle<f64>) -> Illuminance<f64> {
+    let altitude = sun_altitude.to_degrees();
+    let table = &SUN_ALTITUDE_DEGREES_AND_LOG_LUX;
+    let log_lux = if altitude <= table[0].0 {
+        table[0].1
+    } else if altitude >= table[table.len() - 1].0 {
+        table[table.len() - 1].1
+    } else {
+        let upper = table
+            .iter()
+            .position(|(table_altitude, _)| *table_altitude >= altitude)
+            .unwrap_or(table.len() - 1);
+        let (altitude_0, log_lux_0) = table[upper - 1];
+        let (altitude_1, log_lux_1) = table[upper];
+        let t = (altitude - altitude_0) / (altitude_1 - altitude_0);
+        log_lux_0 * (1. - t) + log_lux_1 * t
+    };
+    Illuminance {
+        lux: 10_f64.powf(log_lux),
+    }
+}
+
+const NAKED_EYE_LIMITING_MAGNITUDE: f64 = 6.5;
+
+/*
+ * The apparent magnitude of the faintest star visible to the naked eye against the given sky background.
+ * Each factor of 10 in background brightness costs about 1.25 magnitudes, which reproduces the usual
+ * magnitude 6.5 under a dark sky and only the brightest stars and planets in twilight.
+ */
+pub fn limiting_magnitude(sky_illuminance: Illuminance<f64>) -> f64 {
+    let brightness_ratio = sky_illuminance.lux / NIGHT_SKY_ILLUMINANCE.lux;
+    NAKED_EYE_LIMITING_MAGNITUDE - 1.25 * brightness_ratio.log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::eq_within;
+
+    #[test]
+    fn day_is_vastly_brighter_than_night() {
+        let day = sky_background_illuminance(Angle::from_degrees(45.));
+        let night = sky_background_illuminance(Angle::from_degrees(-30.));
+        assert!(day.lux > 1e6 * night.lux);
+    }
+
+    #[test]
+    fn astronomical_darkness_begins_at_minus_18_degrees() {
+        let boundary = sky_background_illuminance(Angle::from_degrees(-18.));
+        let night = sky_background_illuminance(Angle::from_degrees(-40.));
+        let twilight = sky_background_illuminance(Angle::from_degrees(-17.));
+        assert!(eq_within(boundary.lux, night.lux, 1e-9));
+        assert!(eq_within(night.lux, NIGHT_SKY_ILLUMINANCE.lux, 1e-4));
+        assert!(twilight.lux > night.lux);
+    }
+
+    #[test]
+    fn sky_brightens_monotonically_as_the_sun_rises() {
+        let mut previous = sky_background_illuminance(Angle::from_degrees(-90.));
+        for altitude in -89..=90 {
+            let current = sky_background_illuminance(Angle::from_degrees(altitude as f64));
+            assert!(current.lux >= previous.lux);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn dark_night_has_limiting_magnitude_of_6_5() {
+        let magnitude = limiting_magnitude(sky_background_illuminance(Angle::from_degrees(-30.)));
+        assert!(eq_within(magnitude, 6.5, 0.1));
+    }
+
+    #[test]
+    fn bright_twilight_has_much_lower_limiting_magnitude() {
+        let magnitude = limiting_magnitude(sky_background_illuminance(Angle::from_degrees(-3.)));
+        assert!(magnitude < 3.);
+    }
+}
@@ -0,0 +1,115 @@
+use astro_coords::cartesian::Cartesian;
+use simple_si_units::base::{Distance, Mass, Temperature, Time};
+
+use super::{
+    data::StarData, evolution::StarDataEvolution, physical_parameters::StarPhysicalParameters,
+};
+use crate::{
+    error::AstroUtilError,
+    units::{distance::SOLAR_RADIUS, luminous_intensity::SOLAR_LUMINOUS_INTENSITY},
+};
+
+const SOLAR_TEMPERATURE_IN_KELVIN: f64 = 5772.;
+
+/*
+ * Before this age, the cooling relations below are not meaningful, so younger objects are treated as being this old.
+ */
+const MIN_AGE_IN_YEARS: f64 = 1e6;
+
+/*
+ * The range of masses in solar masses that is covered by the sub-stellar model.
+ * The upper end coincides with the lightest PARSEC model.
+ */
+pub fn substellar_mass_range() -> (Mass<f64>, Mass<f64>) {
+    (Mass::from_solar_mass(0.01), Mass::from_solar_mass(0.09))
+}
+
+/*
+ * The physical parameters of a brown dwarf, which never ignites hydrogen and simply cools down over time.
+ * Luminosity and temperature follow the power laws fitted by Burrows & Liebert (1993):
+ * https://doi.org/10.1103/RevModPhys.65.301
+ * The radius follows from the Stefan-Boltzmann law.
+ * The luminous intensity is scaled with the bolometric luminosity, which overestimates how bright such an object appears to the eye.
+ */
+pub fn substellar_parameters(
+    mass: Mass<f64>,
+    age: Time<f64>,
+) -> Result<StarPhysicalParameters, AstroUtilError> {
+    let (min, max) = substellar_mass_range();
+    if mass.kg.is_nan() || mass < min || mass > max {
+        return Err(AstroUtilError::DataNotAvailable(format!(
+            "Sub-stellar model for {:.3} solar masses (the model covers {} to {})",
+            mass.to_solar_mass(),
+            min.to_solar_mass(),
+            max.to_solar_mass()
+        )));
+    }
+    let mass_factor = mass.to_solar_mass() / 0.05;
+    let age_in_gyr = age.to_yr().max(MIN_AGE_IN_YEARS) / 1e9;
+    let luminosity_in_solar = 4e-5 * mass_factor.powf(2.64) * age_in_gyr.powf(-1.3);
+    let temperature_in_kelvin = 1550. * mass_factor.powf(0.83) * age_in_gyr.powf(-0.32);
+    let radius_in_solar_radii =
+        luminosity_in_solar.sqrt() / (temperature_in_kelvin / SOLAR_TEMPERATURE_IN_KELVIN).powi(2);
+    let radius: Distance<f64> = radius_in_solar_radii * SOLAR_RADIUS;
+    Ok(StarPhysicalParameters::new(
+        Some(mass),
+        Some(radius),
+        luminosity_in_solar * SOLAR_LUMINOUS_INTENSITY,
+        Temperature::from_K(temperature_in_kelvin),
+    ))
+}
+
+/*
+ * A brown dwarf of the given mass and age at pos.
+ */
+pub fn substellar_star_data(
+    mass: Mass<f64>,
+    age: Time<f64>,
+    pos: Cartesian,
+) -> Result<StarData, AstroUtilError> {
+    let params = substellar_parameters(mass, age)?;
+    let evolution = StarDataEvolution::from_age_and_mass(age, mass);
+    Ok(StarData::new("".to_string(), None, params, pos, evolution))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stars::random::parsec::data::ParsecData;
+
+    #[test]
+    fn brown_dwarf_is_fainter_and_cooler_than_lightest_parsec_star() {
+        let parsec_data = ParsecData::test_stub();
+        let age = Time::from_Gyr(4.6);
+        let (lightest_mass, _) = ParsecData::mass_range();
+        let lightest_star = parsec_data
+            .get_star_data(lightest_mass, age, Cartesian::ORIGIN)
+            .unwrap();
+        let brown_dwarf = parsec_data
+            .get_star_data(Mass::from_solar_mass(0.05), age, Cartesian::ORIGIN)
+            .unwrap();
+
+        assert!(
+            brown_dwarf.get_luminous_intensity_at_epoch()
+                < 0.1 * lightest_star.get_luminous_intensity_at_epoch()
+        );
+        assert!(brown_dwarf.get_temperature_at_epoch() < lightest_star.get_temperature_at_epoch());
+        assert!(brown_dwarf.get_temperature_at_epoch() < Temperature::from_K(1500.));
+    }
+
+    #[test]
+    fn brown_dwarfs_cool_down() {
+        let mass = Mass::from_solar_mass(0.05);
+        let young = substellar_parameters(mass, Time::from_Gyr(0.1)).unwrap();
+        let old = substellar_parameters(mass, Time::from_Gyr(10.)).unwrap();
+        assert!(old.temperature() < young.temperature());
+        assert!(old.luminous_intensity() < young.luminous_intensity());
+    }
+
+    #[test]
+    fn masses_outside_the_substellar_range_are_reported() {
+        let age = Time::from_Gyr(1.);
+        assert!(substellar_parameters(Mass::from_solar_mass(0.005), age).is_err());
+        assert!(substellar_parameters(Mass::from_solar_mass(0.5), age).is_err());
+    }
+}
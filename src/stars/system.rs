@@ -0,0 +1,328 @@
+use astro_coords::{direction::Direction, ecliptic::Ecliptic};
+use serde::{Deserialize, Serialize};
+use simple_si_units::{base::Distance, electromagnetic::Illuminance, geometry::Angle};
+
+use super::appearance::StarAppearance;
+use crate::{
+    color::srgb::sRGBColor,
+    coordinates::{
+        earth_equatorial::{ecliptic_to_equatorial, equatorial_to_ecliptic},
+        projection::tangent_basis,
+    },
+    error::AstroUtilError,
+    units::illuminance::IRRADIANCE_ZERO,
+};
+
+/*
+ * A star orbiting the primary of a multiple system, described by where it appears relative to the primary.
+ * Like in double star catalogues, the position angle is measured from celestial north through east.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Companion {
+    pub(crate) name: String,
+    pub(crate) illuminance: Illuminance<f64>,
+    pub(crate) color: sRGBColor,
+    pub(crate) separation: Angle<f64>,
+    pub(crate) position_angle: Angle<f64>,
+}
+
+impl Companion {
+    pub fn new(
+        name: String,
+        illuminance: Illuminance<f64>,
+        color: sRGBColor,
+        separation: Angle<f64>,
+        position_angle: Angle<f64>,
+    ) -> Self {
+        Self {
+            name,
+            illuminance,
+            color,
+            separation,
+            position_angle,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_illuminance(&self) -> &Illuminance<f64> {
+        &self.illuminance
+    }
+
+    pub fn get_color(&self) -> &sRGBColor {
+        &self.color
+    }
+
+    pub fn get_separation(&self) -> &Angle<f64> {
+        &self.separation
+    }
+
+    pub fn get_position_angle(&self) -> &Angle<f64> {
+        &self.position_angle
+    }
+}
+
+/*
+ * A multiple star system, consisting of a primary and at least one companion.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StarSystem {
+    name: String,
+    primary: StarAppearance,
+    companions: Vec<Companion>,
+}
+
+impl StarSystem {
+    pub fn new(
+        name: String,
+        primary: StarAppearance,
+        companions: Vec<Companion>,
+    ) -> Result<Self, AstroUtilError> {
+        if companions.is_empty() {
+            return Err(AstroUtilError::DataNotAvailable(format!(
+                "Companions of star system {}",
+                name
+            )));
+        }
+        Ok(Self {
+            name,
+            primary,
+            companions,
+        })
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_primary(&self) -> &StarAppearance {
+        &self.primary
+    }
+
+    pub fn get_companions(&self) -> &[Companion] {
+        &self.companions
+    }
+
+    pub fn number_of_components(&self) -> usize {
+        1 + self.companions.len()
+    }
+
+    /*
+     * The appearance of the system when its components cannot be told apart.
+     * The illuminances add up, the colour is the illuminance-weighted mean, and the position is that of the primary.
+     */
+    pub fn combined_appearance(&self) -> StarAppearance {
+        let components = std::iter::once((&self.primary.illuminance, &self.primary.color)).chain(
+            self.companions
+                .iter()
+                .map(|companion| (&companion.illuminance, &companion.color)),
+        );
+        let illuminance = components
+            .clone()
+            .fold(IRRADIANCE_ZERO, |total, (illuminance, _)| {
+                total + *illuminance
+            });
+        let color = if illuminance.lux > 0. {
+            components.fold(sRGBColor::from_sRGB(0., 0., 0.), |total, (part, color)| {
+                &total + &(color * (part.lux / illuminance.lux))
+            })
+        } else {
+            self.primary.color.clone()
+        };
+        StarAppearance {
+            name: self.name.clone(),
+            illuminance,
+            color,
+            pos: self.primary.pos.clone(),
+            time_since_epoch: self.primary.time_since_epoch,
+        }
+    }
+
+    /*
+     * The appearances of the primary and all companions, each at its own position on the sky.
+     */
+    pub fn resolved_appearances(&self) -> Vec<StarAppearance> {
+        let primary_dir = self.primary.pos.spherical.to_direction();
+        let mut appearances = vec![self.primary.clone()];
+        for companion in self.companions.iter() {
+            let pos = offset_position(&primary_dir, companion.separation, companion.position_angle)
+                .unwrap_or_else(|_| self.primary.pos.clone());
+            appearances.push(StarAppearance {
+                name: companion.name.clone(),
+                illuminance: companion.illuminance,
+                color: companion.color.clone(),
+                pos,
+                time_since_epoch: self.primary.time_since_epoch,
+            });
+        }
+        appearances
+    }
+}
+
+/*
+ * The position that lies the given angular separation away from dir, towards the given position angle.
+ * The offset is constructed in the equatorial frame, so that the position angle is measured from celestial north.
+ */
+fn offset_position(
+    dir: &Direction,
+    separation: Angle<f64>,
+    position_angle: Angle<f64>,
+) -> Result<Ecliptic, AstroUtilError> {
+    let center = ecliptic_to_equatorial(dir);
+    let (east, north) = tangent_basis(&Direction::new(center[0], center[1], center[2])?);
+    let (sin_sep, cos_sep) = separation.rad.sin_cos();
+    let (sin_pa, cos_pa) = position_angle.rad.sin_cos();
+    let offset: [f64; 3] = std::array::from_fn(|i| {
+        cos_sep * center[i] + sin_sep * (cos_pa * north[i] + sin_pa * east[i])
+    });
+    let offset = equatorial_to_ecliptic(offset)?;
+    Ok(offset.to_cartesian(Distance::from_m(1.)).to_ecliptic()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::{
+            earth_equatorial::right_ascension_and_declination,
+            projection::{components, cross, dot, position_angle},
+        },
+        real_data::stars::all::get_many_stars,
+        units::{
+            angle::{angle_eq_within, angle_from_arcsecs},
+            time::TIME_ZERO,
+        },
+    };
+
+    fn triple_system() -> StarSystem {
+        let primary = StarAppearance::new(
+            "A".to_string(),
+            Illuminance::from_lux(2.0),
+            sRGBColor::from_sRGB(1.0, 0.8, 0.6),
+            Ecliptic::X_DIRECTION,
+            TIME_ZERO,
+        );
+        let companions = vec![
+            Companion::new(
+                "B".to_string(),
+                Illuminance::from_lux(1.0),
+                sRGBColor::from_sRGB(0.6, 0.8, 1.0),
+                angle_from_arcsecs(10.),
+                Angle::from_degrees(63.),
+            ),
+            Companion::new(
+                "C".to_string(),
+                Illuminance::from_lux(0.5),
+                sRGBColor::from_sRGB(0.6, 0.8, 1.0),
+                angle_from_arcsecs(0.5),
+                Angle::from_degrees(300.),
+            ),
+        ];
+        StarSystem::new("Schnuffelpuff".to_string(), primary, companions).unwrap()
+    }
+
+    #[test]
+    fn triple_system_is_brighter_than_each_component() {
+        let system = triple_system();
+        let combined = system.combined_appearance();
+        assert_eq!(system.number_of_components(), 3);
+        for component in system.resolved_appearances() {
+            assert!(combined.illuminance > component.illuminance);
+        }
+        assert!((combined.illuminance.to_lux() - 3.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn companions_appear_at_their_separation() {
+        let system = triple_system();
+        let resolved = system.resolved_appearances();
+        assert_eq!(resolved.len(), 3);
+        for (appearance, companion) in resolved[1..].iter().zip(system.get_companions()) {
+            let primary = components(&system.primary.pos.spherical.to_direction());
+            let secondary = components(&appearance.pos.spherical.to_direction());
+            let sine = cross(&primary, &secondary);
+            let separation = dot(&sine, &sine).sqrt().asin();
+            let difference = (separation - companion.separation.rad).abs();
+            assert!(difference < angle_from_arcsecs(0.05).rad);
+        }
+    }
+
+    #[test]
+    fn position_angles_are_measured_from_celestial_north() {
+        // Separations and position angles of the brightest companions, from the Washington Double Star Catalog.
+        let pairs = [("Almach", 9.6, 63.), ("Algieba", 4.7, 125.)];
+        for (name, separation, position_angle) in pairs {
+            let primary = get_many_stars()
+                .into_iter()
+                .find(|star| star.common_name == name)
+                .unwrap()
+                .to_star_data()
+                .to_star_appearance(TIME_ZERO);
+            let companion = Companion::new(
+                "B".to_string(),
+                Illuminance::from_lux(1.0),
+                sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+                angle_from_arcsecs(separation),
+                Angle::from_degrees(position_angle),
+            );
+            let system = StarSystem::new(name.to_string(), primary, vec![companion]).unwrap();
+            let resolved = system.resolved_appearances();
+            let (ra_a, dec_a) =
+                right_ascension_and_declination(&resolved[0].pos.spherical.to_direction());
+            let (ra_b, dec_b) =
+                right_ascension_and_declination(&resolved[1].pos.spherical.to_direction());
+            let towards_east = (ra_b - ra_a).rad * dec_a.rad.cos();
+            let towards_north = (dec_b - dec_a).rad;
+            let measured = Angle {
+                rad: towards_east.atan2(towards_north),
+            };
+            assert!(
+                angle_eq_within(
+                    measured,
+                    Angle::from_degrees(position_angle),
+                    Angle::from_degrees(0.1)
+                ),
+                "{}: position angle {} instead of {} degrees",
+                name,
+                measured.to_degrees(),
+                position_angle
+            );
+        }
+    }
+
+    #[test]
+    fn position_angle_recovers_the_companion_position_angle() {
+        let primary = get_many_stars()
+            .into_iter()
+            .find(|star| star.common_name == "Almach")
+            .unwrap()
+            .to_star_data()
+            .to_star_appearance(TIME_ZERO);
+        let companion = Companion::new(
+            "B".to_string(),
+            Illuminance::from_lux(1.0),
+            sRGBColor::from_sRGB(1.0, 1.0, 1.0),
+            angle_from_arcsecs(9.6),
+            Angle::from_degrees(63.),
+        );
+        let system = StarSystem::new("Almach".to_string(), primary, vec![companion]).unwrap();
+        let resolved = system.resolved_appearances();
+        let measured = position_angle(
+            &resolved[0].pos.spherical.to_direction(),
+            &resolved[1].pos.spherical.to_direction(),
+        );
+        assert!(angle_eq_within(
+            measured,
+            Angle::from_degrees(63.),
+            Angle::from_degrees(0.01)
+        ));
+    }
+
+    #[test]
+    fn system_without_companions_is_an_error() {
+        let primary = triple_system().primary;
+        assert!(StarSystem::new("Schnuffelpuff".to_string(), primary, vec![]).is_err());
+    }
+}
@@ -51,12 +51,10 @@ pub fn normalized_angle(mut angle: Angle<f64>) -> Angle<f64> {
     angle
 }
 
-#[cfg(test)]
-pub(crate) fn angle_eq_within(
-    actual: Angle<f64>,
-    expected: Angle<f64>,
-    accuracy: Angle<f64>,
-) -> bool {
+/*
+ * Compares two angles modulo a full circle, so that e.g. 0 and 2π are equal.
+ */
+pub fn angle_eq_within(actual: Angle<f64>, expected: Angle<f64>, accuracy: Angle<f64>) -> bool {
     let diff = normalized_angle(actual - expected);
     diff.rad.abs() < accuracy.rad
 }
@@ -89,6 +87,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn angles_are_compared_modulo_full_circle() {
+        let accuracy = angle_from_arcsecs(1.);
+        assert!(angle_eq_within(ANGLE_ZERO, FULL_CIRC, accuracy));
+        assert!(angle_eq_within(-HALF_CIRC, HALF_CIRC, accuracy));
+        assert!(angle_eq_within(
+            Angle::from_degrees(-1.),
+            Angle::from_degrees(359.),
+            accuracy
+        ));
+        assert!(!angle_eq_within(ANGLE_ZERO, HALF_CIRC, accuracy));
+        assert!(!angle_eq_within(
+            ANGLE_ZERO,
+            Angle::from_degrees(1.),
+            accuracy
+        ));
+    }
+
     #[test]
     fn second_angle_roundtrip() {
         for i in -10..10 {
@@ -1,13 +1,22 @@
 use crate::astro_display::AstroDisplay;
 
-use super::DISPLAY_THRESHOLD;
-use simple_si_units::base::Distance;
+use super::{
+    angle::{angle_from_arcsecs, angle_to_arcsecs},
+    time::YEAR,
+    DISPLAY_THRESHOLD,
+};
+use simple_si_units::{
+    base::{Distance, Time},
+    geometry::Angle,
+};
 
 pub const DISTANCE_ZERO: Distance<f64> = Distance { m: 0. };
 pub const EARTH_RADIUS: Distance<f64> = Distance { m: 6.371e6 };
 pub const SOLAR_RADIUS: Distance<f64> = Distance { m: 6.957e8 };
 pub const ASTRONOMICAL_UNIT: Distance<f64> = Distance { m: 1.496e11 };
 pub const LIGHT_YEAR: Distance<f64> = Distance { m: 9.461e15 };
+pub const PARSEC: Distance<f64> = Distance { m: 3.0857e16 };
+pub const KILOPARSEC: Distance<f64> = Distance { m: 1e3 * PARSEC.m };
 
 pub enum DistanceUnit {
     Nanometers,
@@ -19,6 +28,7 @@ pub enum DistanceUnit {
     SunRadii,
     AstronomicalUnits,
     LightYears,
+    Parsecs,
 }
 
 pub fn distance_to_earth_radii(distance: &Distance<f64>) -> f64 {
@@ -29,6 +39,59 @@ pub fn distance_to_sun_radii(distance: &Distance<f64>) -> f64 {
     distance / &SOLAR_RADIUS
 }
 
+pub fn distance_from_light_years(light_years: f64) -> Distance<f64> {
+    light_years * LIGHT_YEAR
+}
+
+pub fn distance_to_light_years(distance: &Distance<f64>) -> f64 {
+    distance / &LIGHT_YEAR
+}
+
+pub fn distance_from_parsecs(parsecs: f64) -> Distance<f64> {
+    parsecs * PARSEC
+}
+
+pub fn distance_to_parsecs(distance: &Distance<f64>) -> f64 {
+    distance / &PARSEC
+}
+
+pub fn distance_from_kiloparsecs(kiloparsecs: f64) -> Distance<f64> {
+    kiloparsecs * KILOPARSEC
+}
+
+pub fn distance_to_kiloparsecs(distance: &Distance<f64>) -> f64 {
+    distance / &KILOPARSEC
+}
+
+/*
+ * The time light needs to cover the distance.
+ * It is consistent with LIGHT_YEAR, so that one light year takes exactly one year.
+ */
+pub fn light_travel_time(distance: Distance<f64>) -> Time<f64> {
+    distance_to_light_years(&distance) * YEAR
+}
+
+/*
+ * The distance of a star whose annual parallax (half the shift across a 2 AU baseline) is the given angle.
+ * Measured parallaxes can be zero or negative due to noise, in which case there is no meaningful distance and None is returned.
+ */
+pub fn distance_from_parallax(parallax: Angle<f64>) -> Option<Distance<f64>> {
+    if parallax.rad.is_nan() || parallax.rad <= 0. {
+        return None;
+    }
+    Some(distance_from_parsecs(1. / angle_to_arcsecs(&parallax)))
+}
+
+/*
+ * The inverse of distance_from_parallax.
+ */
+pub fn distance_to_parallax(distance: Distance<f64>) -> Option<Angle<f64>> {
+    if distance.m.is_nan() || distance.m <= 0. {
+        return None;
+    }
+    Some(angle_from_arcsecs(1. / distance_to_parsecs(&distance)))
+}
+
 pub fn display_distance_in_units(distance: &Distance<f64>, units: DistanceUnit) -> String {
     match units {
         DistanceUnit::Nanometers => format!("{:.2} nm", distance.to_nm()),
@@ -39,13 +102,18 @@ pub fn display_distance_in_units(distance: &Distance<f64>, units: DistanceUnit)
         DistanceUnit::EarthRadii => format!("{:.2} R🜨", distance_to_earth_radii(distance)),
         DistanceUnit::SunRadii => format!("{:.2} R☉", distance_to_sun_radii(distance)),
         DistanceUnit::AstronomicalUnits => format!("{:.2} AU", distance.to_au()),
-        DistanceUnit::LightYears => format!("{:.2} lyr", distance.to_lyr()),
+        DistanceUnit::LightYears => format!("{:.2} ly", distance_to_light_years(distance)),
+        DistanceUnit::Parsecs => format!("{:.2} pc", distance_to_parsecs(distance)),
     }
 }
 
 impl AstroDisplay for Distance<f64> {
     fn astro_display(&self) -> String {
-        let units = if self.to_lyr().abs() > DISPLAY_THRESHOLD {
+        const PARSEC_THRESHOLD: f64 = 1_000.;
+
+        let units = if distance_to_parsecs(self).abs() > PARSEC_THRESHOLD {
+            DistanceUnit::Parsecs
+        } else if distance_to_light_years(self).abs() > DISPLAY_THRESHOLD {
             DistanceUnit::LightYears
         } else if self.to_au().abs() > DISPLAY_THRESHOLD {
             DistanceUnit::AstronomicalUnits
@@ -71,6 +139,7 @@ impl AstroDisplay for Distance<f64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::eq;
 
     #[test]
     fn test_distance_display() {
@@ -87,7 +156,7 @@ mod tests {
         let d = Distance::from_au(1.23);
         assert_eq!(d.astro_display(), "1.23 AU");
         let d = Distance::from_lyr(1.23);
-        assert_eq!(d.astro_display(), "1.23 lyr");
+        assert_eq!(d.astro_display(), "1.23 ly");
     }
 
     #[test]
@@ -105,7 +174,7 @@ mod tests {
         let d = Distance::from_au(-1.23);
         assert_eq!(d.astro_display(), "-1.23 AU");
         let d = Distance::from_lyr(-1.23);
-        assert_eq!(d.astro_display(), "-1.23 lyr");
+        assert_eq!(d.astro_display(), "-1.23 ly");
     }
 
     #[test]
@@ -125,6 +194,86 @@ mod tests {
         let d = Distance::from_au(0.1);
         assert_eq!(d.astro_display(), "0.10 AU");
         let d = Distance::from_lyr(0.1);
-        assert_eq!(d.astro_display(), "0.10 lyr");
+        assert_eq!(d.astro_display(), "0.10 ly");
+    }
+
+    #[test]
+    fn nine_light_years_are_displayed_in_light_years() {
+        let d = distance_from_light_years(9.);
+        assert_eq!(d.astro_display(), "9.00 ly");
+    }
+
+    #[test]
+    fn galactic_distances_are_displayed_in_parsecs() {
+        let d = distance_from_parsecs(1234.);
+        assert_eq!(d.astro_display(), "1234.00 pc");
+        let d = distance_from_parsecs(-1234.);
+        assert_eq!(d.astro_display(), "-1234.00 pc");
+    }
+
+    #[test]
+    fn parallax_of_one_arcsec_is_one_parsec() {
+        let distance = distance_from_parallax(angle_from_arcsecs(1.)).unwrap();
+        assert!(eq(distance_to_parsecs(&distance), 1.));
+        assert!((distance.to_lyr() - 3.26).abs() < 0.01);
+    }
+
+    #[test]
+    fn small_parallaxes_give_large_distances() {
+        let distance = distance_from_parallax(angle_from_arcsecs(1e-3)).unwrap();
+        assert!(eq(distance_to_parsecs(&distance), 1_000.));
+        let smaller = distance_from_parallax(angle_from_arcsecs(1e-4)).unwrap();
+        assert!(smaller > distance);
+    }
+
+    #[test]
+    fn parallax_roundtrip() {
+        let parallax = angle_from_arcsecs(0.379);
+        let distance = distance_from_parallax(parallax).unwrap();
+        let roundtrip = distance_to_parallax(distance).unwrap();
+        assert!(eq(angle_to_arcsecs(&roundtrip), 0.379));
+    }
+
+    #[test]
+    fn non_positive_parallax_has_no_distance() {
+        assert!(distance_from_parallax(angle_from_arcsecs(0.)).is_none());
+        assert!(distance_from_parallax(angle_from_arcsecs(-1e-3)).is_none());
+        assert!(distance_to_parallax(DISTANCE_ZERO).is_none());
+    }
+
+    #[test]
+    fn light_needs_one_year_for_one_light_year() {
+        assert_eq!(light_travel_time(LIGHT_YEAR), YEAR);
+    }
+
+    #[test]
+    fn light_year_roundtrip() {
+        for i in -10..10 {
+            let input = i as f64;
+            let output = distance_to_light_years(&distance_from_light_years(input));
+            assert!(eq(input, output));
+        }
+    }
+
+    #[test]
+    fn parsec_roundtrip() {
+        for i in -10..10 {
+            let input = i as f64;
+            assert!(eq(
+                input,
+                distance_to_parsecs(&distance_from_parsecs(input))
+            ));
+            assert!(eq(
+                input,
+                distance_to_kiloparsecs(&distance_from_kiloparsecs(input))
+            ));
+        }
+    }
+
+    #[test]
+    fn parsec_is_about_3_26_light_years() {
+        let light_years = distance_to_light_years(&distance_from_parsecs(1.));
+        assert!((light_years - 3.2616).abs() < 1e-3);
+        assert!(eq(distance_to_parsecs(&distance_from_kiloparsecs(1.)), 1e3));
     }
 }
@@ -9,13 +9,69 @@ pub const fn from_lux(lux: f64) -> Illuminance<f64> {
     Illuminance { lux }
 }
 
+pub const fn to_lux(illuminance: &Illuminance<f64>) -> f64 {
+    illuminance.lux
+}
+
+/*
+ * Sums up the illuminances of several sources, e.g. to obtain the brightness of a patch of sky.
+ */
+pub fn total_illuminance<'a, I>(illuminances: I) -> Illuminance<f64>
+where
+    I: IntoIterator<Item = &'a Illuminance<f64>>,
+{
+    illuminances
+        .into_iter()
+        .fold(IRRADIANCE_ZERO, |total, illuminance| total + *illuminance)
+}
+
+pub fn scaled_illuminance(illuminance: &Illuminance<f64>, factor: f64) -> Illuminance<f64> {
+    from_lux(illuminance.lux * factor)
+}
+
+/*
+ * A photometric system, defined by the illuminance of a magnitude 0 star.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagnitudeSystem {
+    pub zero_point: Illuminance<f64>,
+}
+
+impl MagnitudeSystem {
+    pub const VISUAL: MagnitudeSystem = MagnitudeSystem {
+        zero_point: APARENT_VISIBLE_MAGNITUDE_ZERO,
+    };
+
+    pub const fn new(zero_point: Illuminance<f64>) -> Self {
+        MagnitudeSystem { zero_point }
+    }
+
+    pub fn apparent_magnitude_to_illuminance(&self, apparent_magnitude: f64) -> Illuminance<f64> {
+        let exponent = apparent_magnitude / -2.5;
+        self.zero_point * 10_f64.powf(exponent)
+    }
+
+    pub fn illuminance_to_apparent_magnitude(&self, illuminance: &Illuminance<f64>) -> f64 {
+        -2.5 * (illuminance / &self.zero_point).log10()
+    }
+}
+
+impl Default for MagnitudeSystem {
+    fn default() -> Self {
+        Self::VISUAL
+    }
+}
+
+/*
+ * Apparent magnitudes are measured relative to APARENT_VISIBLE_MAGNITUDE_ZERO, the illuminance of a magnitude 0 star.
+ * The two conversions below are inverse to each other.
+ */
 pub fn apparent_magnitude_to_illuminance(apparent_magnitude: f64) -> Illuminance<f64> {
-    let exponent = apparent_magnitude / -2.5;
-    APARENT_VISIBLE_MAGNITUDE_ZERO * 10_f64.powf(exponent)
+    MagnitudeSystem::VISUAL.apparent_magnitude_to_illuminance(apparent_magnitude)
 }
 
 pub fn illuminance_to_apparent_magnitude(illuminance: &Illuminance<f64>) -> f64 {
-    -2.5 * (illuminance / &APARENT_VISIBLE_MAGNITUDE_ZERO).log10()
+    MagnitudeSystem::VISUAL.illuminance_to_apparent_magnitude(illuminance)
 }
 
 impl AstroDisplay for Illuminance<f64> {
@@ -36,6 +92,49 @@ mod tests {
 
     const REAL_DATA_TEST_ACCURACY: f64 = 0.05;
 
+    #[test]
+    fn adding_two_one_lux_sources_gives_two_lux() {
+        let sources = [from_lux(1.), from_lux(1.)];
+        assert!(eq(to_lux(&total_illuminance(&sources)), 2.));
+    }
+
+    #[test]
+    fn total_illuminance_of_nothing_is_zero() {
+        let sources: Vec<Illuminance<f64>> = vec![];
+        assert!(eq(to_lux(&total_illuminance(&sources)), 0.));
+    }
+
+    #[test]
+    fn scaling_by_one_half_halves_illuminance() {
+        let illuminance = from_lux(3.);
+        assert!(eq(to_lux(&scaled_illuminance(&illuminance, 0.5)), 1.5));
+    }
+
+    #[test]
+    fn magnitude_zero_is_the_zero_point() {
+        let illuminance = apparent_magnitude_to_illuminance(0.);
+        assert!(eq(illuminance / APARENT_VISIBLE_MAGNITUDE_ZERO, 1.));
+        let magnitude = illuminance_to_apparent_magnitude(&APARENT_VISIBLE_MAGNITUDE_ZERO);
+        assert!(eq(magnitude, 0.));
+    }
+
+    #[test]
+    fn default_magnitude_system_is_visual() {
+        assert_eq!(MagnitudeSystem::default(), MagnitudeSystem::VISUAL);
+    }
+
+    #[test]
+    fn changing_the_zero_point_shifts_magnitudes_by_a_constant() {
+        let shifted = MagnitudeSystem::new(APARENT_VISIBLE_MAGNITUDE_ZERO * 10.);
+        let expected_offset = 2.5;
+        for i in -10..10 {
+            let illuminance = apparent_magnitude_to_illuminance(i as f64);
+            let offset = shifted.illuminance_to_apparent_magnitude(&illuminance)
+                - illuminance_to_apparent_magnitude(&illuminance);
+            assert!(eq(offset, expected_offset));
+        }
+    }
+
     #[test]
     fn apparent_magnitude_roundtrip() {
         for apparent_magnitude in -10..10 {
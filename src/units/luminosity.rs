@@ -8,3 +8,48 @@ pub(crate) fn luminous_intensity_to_luminosity(
 ) -> Luminosity<f64> {
     SOLAR_LUMINOSITY / SOLAR_LUMINOUS_INTENSITY.cd * luminous_intensity
 }
+
+const SOLAR_BOLOMETRIC_MAGNITUDE: f64 = 4.74;
+
+/*
+ * Returns the bolometric luminosity (in Watts, like luminous_intensity_to_luminosity).
+ */
+pub fn bolometric_magnitude_to_luminosity(absolute_bolometric_magnitude: f64) -> Luminosity<f64> {
+    let solar_luminosities =
+        10f64.powf((SOLAR_BOLOMETRIC_MAGNITUDE - absolute_bolometric_magnitude) / 2.5);
+    Luminosity {
+        cd: solar_luminosities * SOLAR_LUMINOSITY,
+    }
+}
+
+pub fn luminosity_to_bolometric_magnitude(luminosity: Luminosity<f64>) -> f64 {
+    SOLAR_BOLOMETRIC_MAGNITUDE - 2.5 * (luminosity.cd / SOLAR_LUMINOSITY).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::eq;
+
+    #[test]
+    fn sun_has_one_solar_luminosity() {
+        let luminosity = bolometric_magnitude_to_luminosity(SOLAR_BOLOMETRIC_MAGNITUDE);
+        assert!(eq(luminosity.cd / SOLAR_LUMINOSITY, 1.));
+    }
+
+    #[test]
+    fn five_magnitudes_brighter_is_hundred_times_as_luminous() {
+        let luminosity = bolometric_magnitude_to_luminosity(SOLAR_BOLOMETRIC_MAGNITUDE - 5.);
+        assert!(eq(luminosity.cd / SOLAR_LUMINOSITY, 100.));
+    }
+
+    #[test]
+    fn bolometric_magnitude_roundtrip() {
+        for i in -10..10 {
+            let input = i as f64;
+            let luminosity = bolometric_magnitude_to_luminosity(input);
+            let output = luminosity_to_bolometric_magnitude(luminosity);
+            assert!(eq(input, output));
+        }
+    }
+}
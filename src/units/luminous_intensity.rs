@@ -26,6 +26,21 @@ pub fn luminous_intensity_to_absolute_magnitude(luminous_intensity: Luminosity<f
     illuminance_to_apparent_magnitude(&illuminance)
 }
 
+/*
+ * The difference between apparent and absolute magnitude of an object at the given distance.
+ */
+pub fn distance_modulus(distance: Distance<f64>) -> f64 {
+    5. * (distance.to_parsec() / 10.).log10()
+}
+
+pub fn absolute_from_apparent_magnitude(apparent_magnitude: f64, distance: Distance<f64>) -> f64 {
+    apparent_magnitude - distance_modulus(distance)
+}
+
+pub fn apparent_from_absolute_magnitude(absolute_magnitude: f64, distance: Distance<f64>) -> f64 {
+    absolute_magnitude + distance_modulus(distance)
+}
+
 pub fn luminous_intensity_to_illuminance(
     luminous_intensity: &Luminosity<f64>,
     distance: &Distance<f64>,
@@ -138,4 +153,29 @@ mod tests {
         let expected = 1.43;
         assert!(eq_within(sun_abs_mag, expected, REAL_DATA_TEST_ACCURACY));
     }
+
+    #[test]
+    fn distance_modulus_at_ten_parsecs_is_zero() {
+        assert!(eq(distance_modulus(Distance::from_parsec(10.)), 0.));
+        assert!(eq(distance_modulus(Distance::from_parsec(100.)), 5.));
+    }
+
+    #[test]
+    fn absolute_magnitude_of_sirius_from_apparent_magnitude() {
+        let apparent_magnitude = -1.46;
+        let distance = Distance::from_lyr(8.6);
+        let absolute_magnitude = absolute_from_apparent_magnitude(apparent_magnitude, distance);
+        assert!(eq_within(absolute_magnitude, 1.42, REAL_DATA_TEST_ACCURACY));
+    }
+
+    #[test]
+    fn apparent_magnitude_roundtrip() {
+        let distance = Distance::from_parsec(123.);
+        for i in -10..10 {
+            let input = i as f64;
+            let absolute_magnitude = absolute_from_apparent_magnitude(input, distance);
+            let output = apparent_from_absolute_magnitude(absolute_magnitude, distance);
+            assert!(eq(input, output));
+        }
+    }
 }
@@ -3,7 +3,7 @@ use std::f64::consts::PI;
 use crate::astro_display::AstroDisplay;
 use simple_si_units::{
     base::Distance,
-    geometry::{Area, SolidAngle},
+    geometry::{Angle, Area, SolidAngle},
 };
 
 pub const SOLID_ANGLE_ZERO: SolidAngle<f64> = SolidAngle { sr: 0.0 };
@@ -37,6 +37,13 @@ pub fn solid_angle_to_area_at_distance(
     solid_angle.sr * (distance * distance)
 }
 
+/*
+ * The diameter of an object that appears under the given angular diameter at the given distance.
+ */
+pub fn physical_size(angular_diameter: Angle<f64>, distance: Distance<f64>) -> Distance<f64> {
+    2. * distance * (angular_diameter.rad / 2.).tan()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +84,17 @@ mod tests {
             radius_and_distance_to_solid_angle(MOON.radius, MOON.orbit.get_semi_major_axis());
         assert!(eq(actual.sr, expected.sr));
     }
+
+    #[test]
+    fn physical_size_of_the_sun() {
+        let size = physical_size(Angle::from_degrees(0.533), Distance::from_au(1.));
+        let solar_diameters = size / (2. * SOLAR_RADIUS);
+        assert!(crate::tests::eq_within(solar_diameters, 1., 0.01));
+    }
+
+    #[test]
+    fn physical_size_at_zero_angle_is_zero() {
+        let size = physical_size(Angle::from_degrees(0.), Distance::from_au(1.));
+        assert!(eq(size.m, 0.));
+    }
 }
@@ -11,6 +11,7 @@ pub(crate) const TEN_MILLENIA: Time<f64> = Time {
     s: 10_000. * 365.25 * 24. * 60. * 60.,
 };
 pub const BILLION_YEARS: Time<f64> = Time { s: 1e9 * YEAR.s };
+pub const JULIAN_CENTURY: Time<f64> = Time { s: 36525. * DAY.s };
 
 pub enum TimeUnit {
     Seconds,